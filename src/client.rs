@@ -2,13 +2,46 @@
 //! * https://geminiprotocol.net/docs/protocol-specification.gmi
 
 pub mod connection;
+pub mod download;
 pub mod error;
+pub mod handler;
+pub mod identity_guard;
+pub mod identity_store;
+pub mod known_hosts;
+pub mod mime_pattern;
+pub mod pinning;
+pub mod session_cache;
+pub mod size_limits;
+pub mod timeouts;
+pub mod timing;
+pub mod tls_policy;
+pub mod validation;
 
 pub use connection::{Connection, Request, Response};
 pub use error::Error;
+pub use handler::Handler;
+pub use identity_guard::IdentityGuard;
+pub use identity_store::IdentityStore;
+pub use known_hosts::KnownHosts;
+pub use pinning::Pinning;
+pub use session_cache::SessionCache;
+pub use size_limits::SizeLimits;
+pub use timeouts::Timeouts;
+pub use timing::Timing;
+pub use tls_policy::TlsPolicy;
+pub use validation::Validation;
 
-use gio::{Cancellable, SocketClient, SocketProtocol, TlsCertificate, prelude::SocketClientExt};
-use glib::Priority;
+use gio::{
+    Cancellable, SocketClient, SocketClientEvent, SocketConnectable, SocketProtocol,
+    TlsCertificate, prelude::SocketClientExt,
+};
+use glib::{
+    Priority,
+    object::{Cast, ObjectExt},
+};
+use std::{cell::Cell, rc::Rc};
+use timeouts::{Phase, Timer};
+use timing::Mark;
 
 // Defaults
 
@@ -20,7 +53,15 @@ pub const DEFAULT_SESSION_RESUMPTION: bool = false;
 /// Provides high-level API for session-safe interaction with
 /// [Gemini](https://geminiprotocol.net) socket server
 pub struct Client {
-    is_session_resumption: bool,
+    handlers: handler::Registry,
+    identity_guard: IdentityGuard,
+    known_hosts: Option<KnownHosts>,
+    pinning: Pinning,
+    session_cache: SessionCache,
+    size_limits: SizeLimits,
+    timeouts: Timeouts,
+    tls_policy: TlsPolicy,
+    validation: Validation,
     pub socket: SocketClient,
 }
 
@@ -39,12 +80,22 @@ impl Client {
         let socket = SocketClient::new();
 
         // Setup initial configuration for Gemini Protocol
+        // * the socket client timeout is not in use, as it can't tell the request phases apart;
+        //   `Connection` applies the `body_idle` one to the socket since the request write,
+        //   see `Timeouts` for details
         socket.set_protocol(SocketProtocol::Tcp);
-        socket.set_timeout(DEFAULT_TIMEOUT);
 
         // Done
         Self {
-            is_session_resumption: DEFAULT_SESSION_RESUMPTION,
+            handlers: handler::Registry::new(),
+            identity_guard: IdentityGuard::default(),
+            known_hosts: None,
+            pinning: Pinning::default(),
+            session_cache: SessionCache::default(),
+            size_limits: SizeLimits::default(),
+            timeouts: Timeouts::default(),
+            tls_policy: TlsPolicy::default(),
+            validation: Validation::default(),
             socket,
         }
     }
@@ -54,6 +105,10 @@ impl Client {
     /// Make new async request to given [Uri](https://docs.gtk.org/glib/struct.Uri.html),
     /// callback with new `Response`on success or `Error` on failure
     /// * compatible with user (certificate) and guest (certificate-less) connection types
+    /// * the client certificate is checked by `IdentityGuard` scopes, including redirect requests,
+    ///   and auto-bound (if enabled) when the response is not the certificate failure
+    /// * apply `Self` timeouts, see `request_with_timeouts_async` to override them
+    /// * collect the `Timing` breakdown, see `Connection::timing` or `Error::timing`
     pub fn request_async(
         &self,
         request: Request,
@@ -63,53 +118,282 @@ impl Client {
         server_certificates: Option<Vec<TlsCertificate>>,
         callback: impl FnOnce(Result<(Response, Connection), Error>) + 'static,
     ) {
+        self.request_with_timeouts_async(
+            request,
+            self.timeouts,
+            priority,
+            cancellable,
+            client_certificate,
+            server_certificates,
+            callback,
+        )
+    }
+
+    /// Make new async request with `timeouts` for this request only
+    /// * see `request_async` for details
+    #[allow(clippy::too_many_arguments)]
+    pub fn request_with_timeouts_async(
+        &self,
+        request: Request,
+        timeouts: Timeouts,
+        priority: Priority,
+        cancellable: Cancellable,
+        client_certificate: Option<TlsCertificate>,
+        server_certificates: Option<Vec<TlsCertificate>>,
+        callback: impl FnOnce(Result<(Response, Connection), Error>) + 'static,
+    ) {
+        // Prevent the client certificate leak out of its scope
+        let client_certificate = match self.identity_guard.check(request.uri(), client_certificate)
+        {
+            Ok(client_certificate) => client_certificate,
+            Err(violation) => return callback(Err(Error::Identity(violation))),
+        };
+
         // Begin new connection
         // * [NetworkAddress](https://docs.gtk.org/gio/class.NetworkAddress.html) required for valid
         //   [SNI](https://geminiprotocol.net/docs/protocol-specification.gmi#server-name-indication)
         match request.to_network_address(crate::DEFAULT_PORT) {
             Ok(network_address) => {
-                self.socket
-                    .connect_async(&network_address.clone(), Some(&cancellable.clone()), {
-                        let is_session_resumption = self.is_session_resumption;
-                        move |result| match result {
-                            Ok(socket_connection) => {
-                                match Connection::build(
-                                    socket_connection.clone(),
-                                    network_address,
-                                    client_certificate,
-                                    server_certificates,
-                                    is_session_resumption,
-                                ) {
-                                    Ok(connection) => connection.clone().request_async(
-                                        request,
-                                        priority,
-                                        cancellable,
-                                        move |result| {
-                                            callback(match result {
-                                                Ok(response) => Ok(response),
-                                                Err(e) => Err(Error::Request(connection, e)),
-                                            })
-                                        },
-                                    ),
-                                    Err(e) => {
-                                        callback(Err(Error::Connection(socket_connection, e)))
+                // The deadline is moving to the `Connection` on success,
+                // to keep it active until the `Body` is complete
+                let deadline = Timer::new(&cancellable, timeouts.deadline);
+                let cancellable = deadline.cancellable().clone();
+                let timer = Timer::new(&cancellable, timeouts.connect);
+                // Collect the resolve and connect timing for this request only
+                let timing = Rc::new(Cell::new(Timing::new()));
+                let event = self.socket.connect_event({
+                    let network_address = network_address.clone();
+                    let timing = timing.clone();
+                    move |_, event, connectable, _| {
+                        if connectable != network_address.upcast_ref::<SocketConnectable>() {
+                            return;
+                        }
+                        let mark = match event {
+                            SocketClientEvent::Resolved => Mark::Resolved,
+                            SocketClientEvent::Connected => Mark::Connected,
+                            _ => return,
+                        };
+                        let mut t = timing.get();
+                        t.mark(mark);
+                        timing.set(t)
+                    }
+                });
+                self.socket.connect_async(
+                    &network_address.clone(),
+                    Some(&timer.cancellable().clone()),
+                    {
+                        let identity_guard = self.identity_guard.clone();
+                        let uri = request.uri().clone();
+                        let tls_policy = self.tls_policy;
+                        let known_hosts = self.known_hosts.clone();
+                        let pinning = self.pinning.clone();
+                        let session_cache = self.session_cache.clone();
+                        let validation = self.validation;
+                        let size_limits = self.size_limits.clone();
+                        let socket = self.socket.clone();
+                        move |result| {
+                            socket.disconnect(event);
+                            let timing = timing.get();
+                            match result {
+                                Ok(socket_connection) => {
+                                    match Connection::build(
+                                        socket_connection.clone(),
+                                        network_address,
+                                        client_certificate.clone(),
+                                        server_certificates,
+                                        tls_policy.is_session_resumption,
+                                    ) {
+                                        Ok(mut connection) => {
+                                            connection.set_known_hosts(known_hosts.as_ref());
+                                            connection.set_pinning(pinning);
+                                            connection.set_validation(validation);
+                                            connection.size_limits = size_limits;
+                                            connection.timeouts = timeouts;
+                                            connection.set_tls_policy(tls_policy);
+                                            if tls_policy.is_session_resumption {
+                                                connection.set_session_cache(Some(session_cache));
+                                            }
+                                            connection.set_deadline(deadline);
+                                            connection.set_timing(timing);
+                                            connection.clone().request_async(
+                                                request,
+                                                priority,
+                                                cancellable,
+                                                move |result| {
+                                                    callback(match result {
+                                                        Ok(response) => {
+                                                            if let Some(ref certificate) =
+                                                                client_certificate
+                                                                && !matches!(
+                                                                    response.0,
+                                                                    Response::Certificate(_)
+                                                                )
+                                                            {
+                                                                identity_guard
+                                                                    .confirm(&uri, certificate)
+                                                            }
+                                                            Ok(response)
+                                                        }
+                                                        Err(e) => {
+                                                            Err(Error::Request(connection, e))
+                                                        }
+                                                    })
+                                                },
+                                            )
+                                        }
+                                        Err(e) => callback(Err(Error::Connection(
+                                            socket_connection,
+                                            e,
+                                            timing,
+                                        ))),
                                     }
                                 }
+                                Err(e) => callback(Err(if timer.is_expired() {
+                                    Error::Timeout(network_address, Phase::Connect, timing)
+                                } else if deadline.is_expired() {
+                                    Error::Timeout(network_address, Phase::Deadline, timing)
+                                } else {
+                                    Error::Connect(network_address, e, timing)
+                                })),
                             }
-                            Err(e) => callback(Err(Error::Connect(network_address, e))),
                         }
-                    })
+                    },
+                )
             }
             Err(e) => callback(Err(Error::NetworkAddress(e))),
         }
     }
 
+    /// Dispatch `Success` response to the most specific `Handler` registered
+    /// * return the `success` untouched with its `connection` and the reason,
+    ///   when no handler matches, so the caller could process it another way
+    /// * see `handler::Registry::resolve` and `handler::dispatch` for details
+    #[allow(clippy::result_large_err)]
+    pub fn dispatch(
+        &self,
+        success: connection::response::Success,
+        connection: Connection,
+        priority: Priority,
+        cancellable: Cancellable,
+    ) -> Result<(), (connection::response::Success, Connection, handler::Error)> {
+        match self.handlers.resolve(&success) {
+            Ok(handler) => {
+                handler::dispatch(handler, success, connection, priority, cancellable);
+                Ok(())
+            }
+            Err(e) => Err((success, connection, e)),
+        }
+    }
+
+    // Getters
+
+    /// Get `Handler` registry by MIME type
+    pub fn handlers(&self) -> &handler::Registry {
+        &self.handlers
+    }
+
+    /// Get client certificate scope guard
+    pub fn identity_guard(&self) -> &IdentityGuard {
+        &self.identity_guard
+    }
+
+    /// Get `KnownHosts` trust store, if any
+    pub fn known_hosts(&self) -> Option<&KnownHosts> {
+        self.known_hosts.as_ref()
+    }
+
+    /// Get TOFU pinning policy
+    pub fn pinning(&self) -> &Pinning {
+        &self.pinning
+    }
+
+    /// Get isolated TLS session cache
+    pub fn session_cache(&self) -> &SessionCache {
+        &self.session_cache
+    }
+
+    /// Get body size limits policy
+    pub fn size_limits(&self) -> &SizeLimits {
+        &self.size_limits
+    }
+
+    /// Get TLS security policy
+    pub fn tls_policy(&self) -> &TlsPolicy {
+        &self.tls_policy
+    }
+
+    /// Get server certificate validation mode
+    pub fn validation(&self) -> Validation {
+        self.validation
+    }
+
+    /// Get request phase timeouts
+    pub fn timeouts(&self) -> &Timeouts {
+        &self.timeouts
+    }
+
     // Setters
 
-    /// Change glib-networking `session-resumption-enabled` property (`false` by default)
+    /// Enable session resumption (`false` by default)
     /// * [Gemini specification](https://geminiprotocol.net/docs/protocol-specification.gmi#client-certificates)
     /// * [GnuTLS manual](https://www.gnutls.org/manual/html_node/Session-resumption.html)
+    /// * the session is resumed by isolated `SessionCache`, for the same host, port
+    ///   and client certificate (or the same absence of one) only
+    /// * shortcut for `TlsPolicy::is_session_resumption`
     pub fn set_session_resumption(&mut self, is_enabled: bool) {
-        self.is_session_resumption = is_enabled
+        self.tls_policy.is_session_resumption = is_enabled
+    }
+
+    /// Set client certificate scope guard, applied to every new request
+    pub fn set_identity_guard(&mut self, identity_guard: IdentityGuard) {
+        self.identity_guard = identity_guard
+    }
+
+    /// Set isolated TLS session cache, e.g. to share it between every `Client` in the application
+    pub fn set_session_cache(&mut self, session_cache: SessionCache) {
+        self.session_cache = session_cache
+    }
+
+    /// Set `KnownHosts` trust store, to verify server certificates by the GIO database path
+    /// * the same store can be shared between every `Client` in the application
+    /// * the `server_certificates` of the request, pinning and validation settings
+    ///   of `Self` are not in use, when the store is set
+    pub fn set_known_hosts(&mut self, known_hosts: Option<KnownHosts>) {
+        self.known_hosts = known_hosts
+    }
+
+    /// Set TOFU pinning and re-trust policy, applied to the `server_certificates` of the request
+    /// * `pinning::Mode::Certificate` for all hosts by default
+    pub fn set_pinning(&mut self, pinning: Pinning) {
+        self.pinning = pinning
+    }
+
+    /// Set `Handler` registry by MIME type, used by `dispatch` method
+    pub fn set_handlers(&mut self, handlers: handler::Registry) {
+        self.handlers = handlers
+    }
+
+    /// Set body size limits policy by MIME type (unlimited by default)
+    /// * applied by the `Body` helpers after the success header is parsed
+    pub fn set_size_limits(&mut self, size_limits: SizeLimits) {
+        self.size_limits = size_limits
+    }
+
+    /// Set request phase timeouts, applied to every new request
+    /// * use `request_with_timeouts_async` to override them for single request
+    pub fn set_timeouts(&mut self, timeouts: Timeouts) {
+        self.timeouts = timeouts
+    }
+
+    /// Set server certificate validation mode (`Validation::Tofu` by default)
+    /// * the detailed result is available by `Connection::verification`
+    pub fn set_validation(&mut self, validation: Validation) {
+        self.validation = validation
+    }
+
+    /// Set TLS security policy: minimum protocol version, `close_notify` handling
+    /// and session resumption
+    pub fn set_tls_policy(&mut self, tls_policy: TlsPolicy) {
+        self.tls_policy = tls_policy
     }
 }
@@ -1,25 +1,56 @@
 pub mod error;
+pub mod info;
 pub mod request;
 pub mod response;
+pub mod trust;
 
 pub use error::Error;
+pub use info::ConnectionInfo;
 pub use request::{Mode, Request};
 pub use response::Response;
+pub use trust::Trust;
 
+use crate::client::{
+    KnownHosts,
+    pinning::Pinning,
+    session_cache::{self, SessionCache},
+    timeouts::{Phase, Timeouts, Timer},
+    timing::{Mark, Timing},
+    tls_policy::{CloseNotify, TlsPolicy},
+    validation::{Validation, Verification},
+};
 use gio::{
     Cancellable, IOStream, NetworkAddress, SocketConnection, TlsCertificate, TlsClientConnection,
-    prelude::{IOStreamExt, OutputStreamExtManual, TlsCertificateExt, TlsConnectionExt},
+    TlsDatabase,
+    prelude::{
+        IOStreamExt, NetworkAddressExt, OutputStreamExtManual, SocketConnectionExt, SocketExt,
+        TlsConnectionExt,
+    },
 };
 use glib::{
     Bytes, Priority,
     object::{Cast, ObjectExt},
 };
+use std::{cell::Cell, rc::Rc};
 
 #[derive(Debug, Clone)]
 pub struct Connection {
+    deadline: Option<Rc<Timer>>,
+    /// Server closed the connection without `close_notify`, by `CloseNotify::Reported` policy
+    is_close_notify_missing: Rc<Cell<bool>>,
     pub network_address: NetworkAddress,
+    /// Isolated TLS session cache with the `Key` of `Self`, if enabled
+    session_cache: Option<(SessionCache, session_cache::Key)>,
+    /// Body size limits policy, applied by the `Body` helpers
+    pub size_limits: crate::client::SizeLimits,
     pub socket_connection: SocketConnection,
+    /// Request phase timeouts, applied by `request_async` and the `Body` helpers
+    pub timeouts: Timeouts,
+    timing: Rc<Cell<Timing>>,
     pub tls_client_connection: TlsClientConnection,
+    tls_policy: TlsPolicy,
+    /// Server certificate trust state, applied by the handshake
+    trust: Rc<Trust>,
 }
 
 impl Connection {
@@ -33,12 +64,14 @@ impl Connection {
         server_certificates: Option<Vec<TlsCertificate>>,
         is_session_resumption: bool,
     ) -> Result<Self, Error> {
+        let trust = Rc::new(Trust::default());
         Ok(Self {
             tls_client_connection: match new_tls_client_connection(
                 &socket_connection,
                 Some(&network_address),
                 server_certificates,
                 is_session_resumption,
+                trust.clone(),
             ) {
                 Ok(tls_client_connection) => {
                     if let Some(ref c) = client_certificate {
@@ -48,8 +81,19 @@ impl Connection {
                 }
                 Err(e) => return Err(e),
             },
+            deadline: None,
+            is_close_notify_missing: Rc::new(Cell::new(false)),
             network_address,
+            session_cache: None,
+            size_limits: crate::client::SizeLimits::default(),
             socket_connection,
+            timeouts: Timeouts::default(),
+            timing: Rc::new(Cell::new(Timing::new())),
+            tls_policy: TlsPolicy {
+                is_session_resumption,
+                ..TlsPolicy::default()
+            },
+            trust,
         })
     }
 
@@ -58,6 +102,7 @@ impl Connection {
     /// Send new `Request` to `Self` connection using
     /// [Gemini](https://geminiprotocol.net/docs/protocol-specification.gmi) or
     /// [Titan](gemini://transjovian.org/titan/page/The%20Titan%20Specification) protocol
+    /// * performs the TLS handshake first, to apply `Timeouts` to every phase separately
     pub fn request_async(
         self,
         request: Request,
@@ -65,6 +110,53 @@ impl Connection {
         cancellable: Cancellable,
         callback: impl FnOnce(Result<(Response, Self), Error>) + 'static,
     ) {
+        let timer = Timer::new(&cancellable, self.timeouts.handshake);
+        self.tls_client_connection.clone().handshake_async(
+            priority,
+            Some(&timer.cancellable().clone()),
+            move |result| match result {
+                Ok(()) => {
+                    self.mark(Mark::Handshaken);
+                    if let Err(e) = self
+                        .tls_policy
+                        .check_version(self.tls_client_connection.protocol_version())
+                    {
+                        return callback(Err(Error::Policy(e)));
+                    }
+                    if let Some((ref session_cache, ref key)) = self.session_cache {
+                        session_cache.store(key.clone(), self.tls_client_connection.clone())
+                    }
+                    self.write_async(request, priority, cancellable, callback)
+                }
+                Err(e) => {
+                    // the session state may be the failure reason
+                    if let Some((ref session_cache, ref key)) = self.session_cache {
+                        session_cache.remove(key)
+                    }
+                    callback(Err(match self.expired(&timer, Phase::Handshake) {
+                        Some(phase) => Error::Timeout(phase),
+                        None => match self.trust.verification() {
+                            Some(v) if !v.is_accepted => match v.mismatch() {
+                                Some(mismatch) if v.ca.is_none() => Error::Mismatch(mismatch),
+                                _ => Error::Validation(v),
+                            },
+                            _ => Error::Handshake(e),
+                        },
+                    }))
+                }
+            },
+        )
+    }
+
+    /// Write `Request` bytes to `Self` connection, then read the `Response` header
+    fn write_async(
+        self,
+        request: Request,
+        priority: Priority,
+        cancellable: Cancellable,
+        callback: impl FnOnce(Result<(Response, Self), Error>) + 'static,
+    ) {
+        self.set_idle_timeout();
         let output_stream = self.stream().output_stream();
         // Make sure **all header bytes** sent to the destination
         // > A partial write is performed with the size of a message block, which is 16kB
@@ -76,17 +168,10 @@ impl Connection {
             move |result| match result {
                 Ok(_) => match request {
                     Request::Gemini { mode, .. } => match mode {
-                        Mode::HeaderOnly => Response::header_from_connection_async(
-                            self,
-                            priority,
-                            cancellable,
-                            |result, connection| {
-                                callback(match result {
-                                    Ok(response) => Ok((response, connection)),
-                                    Err(e) => Err(Error::Response(e)),
-                                })
-                            },
-                        ),
+                        Mode::HeaderOnly => {
+                            self.mark(Mark::RequestSent);
+                            self.response_async(priority, cancellable, callback)
+                        }
                     },
                     // Make sure **all data bytes** sent to the destination
                     // > A partial write is performed with the size of a message block, which is 16kB
@@ -97,23 +182,61 @@ impl Connection {
                         Some(&cancellable.clone()),
                         move |result| match result {
                             Ok(_) => match mode {
-                                Mode::HeaderOnly => Response::header_from_connection_async(
-                                    self,
-                                    priority,
-                                    cancellable,
-                                    |result, connection| {
-                                        callback(match result {
-                                            Ok(response) => Ok((response, connection)),
-                                            Err(e) => Err(Error::Response(e)),
-                                        })
-                                    },
-                                ),
+                                Mode::HeaderOnly => {
+                                    self.mark(Mark::RequestSent);
+                                    self.response_async(priority, cancellable, callback)
+                                }
                             },
-                            Err((b, e)) => callback(Err(Error::Request(b, e))),
+                            Err((b, e)) => callback(Err(self.request_error(b, e))),
                         },
                     ),
                 },
-                Err((b, e)) => callback(Err(Error::Request(b, e))),
+                Err((b, e)) => callback(Err(self.request_error(b, e))),
+            },
+        )
+    }
+
+    /// Read `Response` header from `Self` connection,
+    /// apply the `header_first_byte` and `header` timeouts
+    fn response_async(
+        self,
+        priority: Priority,
+        cancellable: Cancellable,
+        callback: impl FnOnce(Result<(Response, Self), Error>) + 'static,
+    ) {
+        let first_byte = Rc::new(Timer::new(&cancellable, self.timeouts.header_first_byte));
+        let header = Timer::new(first_byte.cancellable(), self.timeouts.header);
+        let timing = self.timing.clone();
+        Response::header_from_connection_progress_async(
+            self,
+            priority,
+            header.cancellable().clone(),
+            {
+                let first_byte = first_byte.clone();
+                move |total| {
+                    if total == 1 {
+                        first_byte.stop();
+                        mark(&timing, Mark::HeaderFirstByte)
+                    }
+                }
+            },
+            move |result, connection| {
+                callback(match result {
+                    Ok(response) => {
+                        connection.mark(Mark::HeaderComplete);
+                        connection.set_idle_timeout();
+                        Ok((response, connection))
+                    }
+                    Err(e) => Err(
+                        match connection
+                            .expired(&first_byte, Phase::HeaderFirstByte)
+                            .or_else(|| connection.expired(&header, Phase::Header))
+                        {
+                            Some(phase) => Error::Timeout(phase),
+                            None => Error::Response(e),
+                        },
+                    ),
+                })
             },
         )
     }
@@ -125,12 +248,176 @@ impl Connection {
     /// * useful to keep `Connection` reference active in async I/O context
     pub fn stream(&self) -> IOStream {
         self.tls_client_connection.clone().upcast::<IOStream>()
-        // * also `base_io_stream` method available @TODO
+        // * see `info` for the base `SocketConnection` details
+    }
+
+    /// Get server certificate `Verification` result of the handshake, if any
+    pub fn verification(&self) -> Option<Verification> {
+        self.trust.verification().or_else(|| {
+            self.trust
+                .known_hosts()?
+                .verification(&self.tls_client_connection)
+        })
+    }
+
+    /// Get connection and TLS session details, collected by this moment
+    pub fn info(&self) -> ConnectionInfo {
+        ConnectionInfo::from_connection(self)
+    }
+
+    /// Get timing breakdown for the request, collected by this moment
+    pub fn timing(&self) -> Timing {
+        self.timing.get()
+    }
+
+    /// Get TLS security policy
+    pub fn tls_policy(&self) -> TlsPolicy {
+        self.tls_policy
+    }
+
+    /// Check the session resumption is enabled for `Self`,
+    /// by glib-networking property or isolated `SessionCache`
+    pub fn is_session_resumption_enabled(&self) -> bool {
+        self.session_cache.is_some()
+            || self
+                .tls_client_connection
+                .property("session-resumption-enabled")
+    }
+
+    /// Check the server closed the connection without `close_notify`
+    /// * reported by the `Body` helpers with `CloseNotify::Reported` policy only
+    pub fn is_close_notify_missing(&self) -> bool {
+        self.is_close_notify_missing.get()
+    }
+
+    /// Check the total request deadline is expired
+    pub fn is_deadline_expired(&self) -> bool {
+        self.deadline
+            .as_ref()
+            .is_some_and(|deadline| deadline.is_expired())
+    }
+
+    // Setters
+
+    /// Record given timing `Mark` by the current time
+    /// * the timing is shared by all `Self` clones, including the `Body` owner
+    pub fn mark(&self, mark: Mark) {
+        self::mark(&self.timing, mark)
+    }
+
+    /// Apply the `body_idle` timeout of `Self` to the socket, as the I/O idle timeout
+    /// * called before the request write, on the header complete and by `Body::new`,
+    ///   so the stalled server can't hang any consumer of `stream`
+    pub fn set_idle_timeout(&self) {
+        self.socket_connection
+            .socket()
+            .set_timeout(self.timeouts.body_idle_secs())
+    }
+
+    /// Mark the connection closed without `close_notify`
+    pub fn set_close_notify_missing(&self) {
+        self.is_close_notify_missing.set(true)
+    }
+
+    /// Change TLS security policy, before the handshake
+    pub fn set_tls_policy(&mut self, tls_policy: TlsPolicy) {
+        self.tls_client_connection
+            .set_require_close_notify(tls_policy.close_notify != CloseNotify::Ignored);
+        self.tls_client_connection.set_property(
+            "session-resumption-enabled",
+            tls_policy.is_session_resumption,
+        );
+        self.tls_policy = tls_policy
+    }
+
+    /// Resume the TLS session by isolated `SessionCache`, before the handshake
+    /// * the glib-networking session resumption is disabled, as it is not isolated
+    ///   by the client certificate
+    /// * the session is cached by `session_cache::Key` of `Self` on the handshake success
+    /// * the session is not cached, if the client certificate fingerprint is not available
+    /// * call after the client certificate is set
+    pub fn set_session_cache(&mut self, session_cache: Option<SessionCache>) {
+        self.tls_client_connection
+            .set_property("session-resumption-enabled", false);
+        self.session_cache = session_cache.and_then(|session_cache| {
+            let key = session_cache::Key::new(
+                &self.network_address,
+                self.tls_client_connection.certificate().as_ref(),
+            )?;
+            session_cache.resume(&key, &self.tls_client_connection);
+            Some((session_cache, key))
+        })
+    }
+
+    /// Change TOFU pinning policy, applied by the handshake
+    pub fn set_pinning(&self, pinning: Pinning) {
+        self.trust.set_pinning(pinning)
+    }
+
+    /// Verify server certificates by `KnownHosts` database, set as the TLS connection database
+    /// * the `server_certificates`, pinning and validation settings of `Self` are not in use
+    pub fn set_known_hosts(&self, known_hosts: Option<&KnownHosts>) {
+        self.trust
+            .set_known_hosts(known_hosts, &self.tls_client_connection)
+    }
+
+    /// Change server certificate validation mode, applied by the handshake
+    pub fn set_validation(&self, validation: Validation) {
+        self.trust.set_validation(validation)
+    }
+
+    /// Replace the timing collected, e.g. with one started before `Self` is built
+    pub fn set_timing(&mut self, timing: Timing) {
+        self.timing.set(timing)
+    }
+
+    /// Keep the total request deadline `Timer` active until `Self` is dropped
+    /// * close the socket on expire, to interrupt any pending I/O, including the body reading
+    pub fn set_deadline(&mut self, deadline: Timer) {
+        deadline.connect_expired({
+            let socket_connection = self.socket_connection.downgrade();
+            move || {
+                if let Some(socket_connection) = socket_connection.upgrade() {
+                    let _ = socket_connection.socket().close();
+                }
+            }
+        });
+        self.deadline = Some(Rc::new(deadline))
+    }
+
+    // Tools
+
+    /// Get expired timeout `Phase` for the operation failed, if any
+    /// * `phase` for given `timer` or `Phase::Deadline` for the total request deadline
+    fn expired(&self, timer: &Timer, phase: Phase) -> Option<Phase> {
+        if timer.is_expired() {
+            Some(phase)
+        } else if self.is_deadline_expired() {
+            Some(Phase::Deadline)
+        } else {
+            None
+        }
+    }
+
+    /// Build `Error` for the failed `Request` write operation
+    fn request_error(&self, bytes: Bytes, e: glib::Error) -> Error {
+        if self.is_deadline_expired() {
+            Error::Timeout(Phase::Deadline)
+        } else {
+            Error::Request(bytes, e)
+        }
     }
 }
 
 // Tools
 
+/// Record timing `Mark` by the current time
+fn mark(timing: &Cell<Timing>, mark: Mark) {
+    let mut t = timing.get();
+    t.mark(mark);
+    timing.set(t)
+}
+
 /// Setup new [TlsClientConnection](https://docs.gtk.org/gio/iface.TlsClientConnection.html)
 /// wrapper for [SocketConnection](https://docs.gtk.org/gio/class.SocketConnection.html)
 /// using `server_identity` as the [SNI](https://geminiprotocol.net/docs/protocol-specification.gmi#server-name-indication)
@@ -139,8 +426,11 @@ fn new_tls_client_connection(
     server_identity: Option<&NetworkAddress>,
     server_certificates: Option<Vec<TlsCertificate>>,
     is_session_resumption: bool,
+    trust: Rc<Trust>,
 ) -> Result<TlsClientConnection, Error> {
-    match TlsClientConnection::new(socket_connection, server_identity) {
+    // copy the identity, to make it unique for the `KnownHosts` results of this connection
+    let identity = server_identity.map(|a| NetworkAddress::new(&a.hostname(), a.port()));
+    match TlsClientConnection::new(socket_connection, identity.as_ref()) {
         Ok(tls_client_connection) => {
             // Prevent session resumption (certificate change ability in runtime)
             tls_client_connection.set_property("session-resumption-enabled", is_session_resumption);
@@ -152,17 +442,11 @@ fn new_tls_client_connection(
             tls_client_connection.set_require_close_notify(true);
 
             // [TOFU](https://geminiprotocol.net/docs/protocol-specification.gmi#tls-server-certificate-validation)
-            tls_client_connection.connect_accept_certificate(move |_, c, _| {
-                server_certificates
-                    .as_ref()
-                    .is_none_or(|server_certificates| {
-                        for server_certificate in server_certificates {
-                            if server_certificate.is_same(c) {
-                                return true;
-                            }
-                        }
-                        false
-                    })
+            // * unset the database, to handle every certificate by `Trust`, including CA signed
+            // * `Trust` records the `Verification` result, to report it on the handshake failure
+            tls_client_connection.set_database(None::<&TlsDatabase>);
+            tls_client_connection.connect_accept_certificate(move |t, c, _| {
+                trust.accept(t, identity.as_ref(), server_certificates.as_deref(), c)
             });
 
             Ok(tls_client_connection)
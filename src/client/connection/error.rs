@@ -2,23 +2,47 @@ use std::fmt::{Display, Formatter, Result};
 
 #[derive(Debug)]
 pub enum Error {
+    Handshake(glib::Error),
+    Mismatch(crate::client::pinning::Mismatch),
+    Policy(crate::client::tls_policy::Error),
     Request(glib::Bytes, glib::Error),
     Response(crate::client::connection::response::Error),
+    Timeout(crate::client::timeouts::Phase),
     TlsClientConnection(glib::Error),
+    Validation(crate::client::validation::Verification),
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter) -> Result {
         match self {
+            Self::Handshake(e) => {
+                write!(f, "TLS handshake error: {e}")
+            }
+            Self::Mismatch(e) => {
+                write!(f, "TOFU error: {e}")
+            }
+            Self::Policy(e) => {
+                write!(f, "TLS policy error: {e}")
+            }
             Self::Request(_, e) => {
                 write!(f, "Request error: {e}")
             }
             Self::Response(e) => {
                 write!(f, "Response error: {e}")
             }
+            Self::Timeout(phase) => {
+                write!(f, "Timeout on {phase} phase")
+            }
             Self::TlsClientConnection(e) => {
                 write!(f, "TLS client connection error: {e}")
             }
+            Self::Validation(v) => {
+                write!(f, "Server certificate validation error: {:?}", v.ca)?;
+                if let Some(mismatch) = v.mismatch() {
+                    write!(f, ", TOFU error: {mismatch}")?;
+                }
+                Ok(())
+            }
         }
     }
 }
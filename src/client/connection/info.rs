@@ -0,0 +1,73 @@
+use super::Connection;
+use crate::client::validation::Verification;
+use gio::{
+    InetSocketAddress, SocketAddress, TlsCertificate, TlsCertificateFlags, TlsProtocolVersion,
+    prelude::{SocketConnectionExt, TlsConnectionExt},
+};
+use glib::{GString, object::Cast};
+
+/// Connection and TLS session details, e.g. for the page info dialog
+/// * the TLS values are available after the handshake only
+#[derive(Debug, Clone)]
+pub struct ConnectionInfo {
+    /// Remote address actually connected (e.g. one IP from the multi-address host)
+    pub remote_address: Option<InetSocketAddress>,
+    pub local_address: Option<InetSocketAddress>,
+    /// Negotiated TLS protocol version, `Unknown` before the handshake
+    pub protocol_version: TlsProtocolVersion,
+    /// Negotiated TLS cipher suite name, e.g. `TLS_AES_256_GCM_SHA384`
+    pub ciphersuite_name: Option<GString>,
+    pub peer_certificate: Option<TlsCertificate>,
+    pub peer_certificate_errors: TlsCertificateFlags,
+    /// Client certificate was offered to the server
+    /// * GIO does not report if the server actually requested it
+    pub is_client_certificate: bool,
+    /// Session resumption was enabled for this connection (see `SessionCache`)
+    /// * GIO does not report if the session was actually resumed,
+    ///   so `true` means the resumption was attempted only
+    pub is_session_resumption_enabled: bool,
+    /// Server certificate verification result, `None` before the handshake
+    pub verification: Option<Verification>,
+}
+
+impl ConnectionInfo {
+    // Constructors
+
+    /// Create new `Self` for given `Connection`
+    pub fn from_connection(connection: &Connection) -> Self {
+        let tls = &connection.tls_client_connection;
+        Self {
+            remote_address: inet(connection.socket_connection.remote_address()),
+            local_address: inet(connection.socket_connection.local_address()),
+            protocol_version: tls.protocol_version(),
+            ciphersuite_name: tls.ciphersuite_name(),
+            peer_certificate: tls.peer_certificate(),
+            peer_certificate_errors: tls.peer_certificate_errors(),
+            is_client_certificate: tls.certificate().is_some(),
+            is_session_resumption_enabled: connection.is_session_resumption_enabled(),
+            verification: connection.verification(),
+        }
+    }
+}
+
+// Tools
+
+/// Get `InetSocketAddress` from the socket address result, if any
+fn inet(address: Result<SocketAddress, glib::Error>) -> Option<InetSocketAddress> {
+    address.ok()?.downcast().ok()
+}
+
+#[test]
+fn test() {
+    use gio::{InetAddress, SocketFamily, UnixSocketAddress};
+
+    let address = InetSocketAddress::new(&InetAddress::new_loopback(SocketFamily::Ipv4), 1965);
+    assert_eq!(inet(Ok(address.clone().upcast())), Some(address));
+    assert!(
+        inet(Ok(
+            UnixSocketAddress::new(std::path::Path::new("/tmp/s")).upcast()
+        ))
+        .is_none()
+    );
+    assert!(inet(Err(glib::Error::new(gio::IOErrorEnum::NotConnected, "e"))).is_none());
+}
@@ -34,12 +34,31 @@ impl Response {
         priority: Priority,
         cancellable: Cancellable,
         callback: impl FnOnce(Result<Self, Error>, Connection) + 'static,
+    ) {
+        Self::header_from_connection_progress_async(
+            connection,
+            priority,
+            cancellable,
+            |_| {},
+            callback,
+        )
+    }
+
+    /// Asynchronously create new `Self` for given `Connection`
+    /// * `on_byte` reports the header bytes total, e.g. to stop the first byte timeout
+    pub(super) fn header_from_connection_progress_async(
+        connection: Connection,
+        priority: Priority,
+        cancellable: Cancellable,
+        on_byte: impl Fn(usize) + 'static,
+        callback: impl FnOnce(Result<Self, Error>, Connection) + 'static,
     ) {
         header_from_stream_async(
             Vec::with_capacity(HEADER_LEN),
             connection.stream(),
             cancellable,
             priority,
+            on_byte,
             |result| {
                 callback(
                     match result {
@@ -89,6 +108,7 @@ fn header_from_stream_async(
     stream: impl IsA<IOStream>,
     cancellable: Cancellable,
     priority: Priority,
+    on_byte: impl Fn(usize) + 'static,
     callback: impl FnOnce(Result<Vec<u8>, Error>) + 'static,
 ) {
     use gio::prelude::{IOStreamExt, InputStreamExtManual};
@@ -101,6 +121,7 @@ fn header_from_stream_async(
                 if size == 0 {
                     return callback(Ok(buffer));
                 }
+                on_byte(buffer.len() + size);
                 if buffer.len() + bytes.len() > HEADER_LEN {
                     buffer.extend(bytes);
                     return callback(Err(Error::Protocol(buffer)));
@@ -112,6 +133,7 @@ fn header_from_stream_async(
                         stream,
                         cancellable,
                         priority,
+                        on_byte,
                         callback,
                     );
                 }
@@ -120,7 +142,7 @@ fn header_from_stream_async(
                     return callback(Ok(buffer));
                 }
                 buffer.extend(bytes);
-                header_from_stream_async(buffer, stream, cancellable, priority, callback)
+                header_from_stream_async(buffer, stream, cancellable, priority, on_byte, callback)
             }
             Err((data, e)) => callback(Err(Error::Stream(e, data))),
         },
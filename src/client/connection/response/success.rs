@@ -1,9 +1,13 @@
+pub mod body;
 pub mod default;
 pub mod error;
 
+pub use body::Body;
 pub use default::Default;
 pub use error::Error;
 
+use default::Header;
+
 const CODE: u8 = b'2';
 
 pub enum Success {
@@ -54,6 +58,16 @@ impl Success {
                 .map_err(|e| Error::Default(default::Error::Header(e))),
         }
     }
+
+    // Converters
+
+    /// Convert `Self` into the header and `Body` owning given `Connection`
+    /// * useful for the requests constructed with `Mode::HeaderOnly` flag
+    pub fn into_body(self, connection: crate::client::Connection) -> (Header, Body) {
+        match self {
+            Self::Default(default) => default.into_body(connection),
+        }
+    }
 }
 
 #[test]
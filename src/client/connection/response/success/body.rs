@@ -0,0 +1,432 @@
+pub mod error;
+pub mod stream;
+
+pub use error::Error;
+pub use stream::Stream;
+
+use crate::{
+    client::{Connection, timeouts::Phase, timing::Mark, tls_policy::CloseNotify},
+    gio::{
+        bounded_input_stream::BoundedInputStream,
+        content_type::{Sniff, sniff},
+        file_download,
+        output_stream::{self, Size},
+        subprocess::{self, Exit},
+    },
+};
+use gio::{
+    Cancellable, File, IOErrorEnum, InputStream, OutputStream, TlsError,
+    prelude::{FileExt, IOStreamExt, InputStreamExt, OutputStreamExtManual},
+};
+use glib::{
+    Bytes, ChecksumType, Priority,
+    object::{Cast, IsA},
+};
+use std::ffi::OsStr;
+
+/// Default chunk size for `read_to_bytes_async`
+const CHUNK: usize = 0x10000; // 64KB
+
+/// Content holder for [Success](https://geminiprotocol.net/docs/protocol-specification.gmi#success) response
+/// * owns the `Connection` to keep it active until the body bytes are processed
+/// * includes the body bytes, received together with the header (if any)
+/// * applies the bytes `limit` (e.g. by `SizeLimits` policy) to all read helpers
+/// * applies the `body_idle` timeout of the `Connection` to the socket
+pub struct Body {
+    buffer: Vec<u8>,
+    connection: Connection,
+    limit: Option<usize>,
+}
+
+impl Body {
+    // Constructors
+
+    /// Create new `Self` for given `Connection`
+    /// * `buffer` is the body bytes received together with the header
+    /// * `limit` is max bytes total, `None` for unlimited
+    pub fn new(connection: Connection, buffer: Vec<u8>, limit: Option<usize>) -> Self {
+        connection.set_idle_timeout();
+        Self {
+            buffer,
+            connection,
+            limit,
+        }
+    }
+
+    // Actions
+
+    /// Asynchronously read all body bytes into the memory
+    /// * return `Err` if the bytes total reached the `limit` (`None` for unlimited)
+    /// * `Self` limit (by `SizeLimits` policy) is applied too, if stricter;
+    ///   use `set_limit` to change it
+    /// * return `Error::Truncated` with all bytes received on the missing `close_notify`,
+    ///   by `CloseNotify::Required` policy
+    pub fn read_to_bytes_async(
+        self,
+        limit: Option<usize>,
+        priority: Priority,
+        cancellable: Cancellable,
+        callback: impl FnOnce(Result<Bytes, Error>) + 'static,
+    ) {
+        let limit = min(limit, self.limit);
+        if let Some(limit) = limit
+            && self.buffer.len() > limit
+        {
+            return callback(Err(Error::BytesTotal(self.buffer.len(), limit)));
+        }
+        read_to_vec_async(
+            self.buffer,
+            self.connection.clone(),
+            limit,
+            priority,
+            cancellable,
+            move |result| {
+                callback(match result {
+                    Ok(buffer) => {
+                        self.connection.mark(Mark::BodyComplete);
+                        Ok(Bytes::from_owned(buffer))
+                    }
+                    Err(e) => Err(map_err(&self.connection, e)),
+                })
+            },
+        )
+    }
+
+    /// Asynchronously copy all body bytes to given [OutputStream](https://docs.gtk.org/gio/class.OutputStream.html)
+    /// * return bytes total on success
+    /// * the `output_stream` is not closing on complete
+    pub fn copy_to_async(
+        self,
+        output_stream: impl IsA<OutputStream>,
+        priority: Priority,
+        cancellable: Cancellable,
+        callback: impl FnOnce(Result<usize, Error>) + 'static,
+    ) {
+        let Self {
+            buffer,
+            connection,
+            limit,
+        } = self;
+        let size = Size {
+            total: buffer.len(),
+            limit,
+            ..Size::default()
+        };
+        if let Some(limit) = size.limit
+            && size.total > limit
+        {
+            return callback(Err(Error::BytesTotal(size.total, limit)));
+        }
+        // Make sure **all buffered bytes** sent to the destination
+        output_stream.clone().write_all_async(
+            buffer,
+            priority,
+            Some(&cancellable.clone()),
+            move |result| match result {
+                Ok(_) => output_stream::from_stream_async(
+                    connection.clone().stream(),
+                    output_stream,
+                    cancellable,
+                    priority,
+                    size,
+                    (
+                        |_, _| {},
+                        move |result| {
+                            callback(match result {
+                                Ok((_, total)) => {
+                                    connection.mark(Mark::BodyComplete);
+                                    Ok(total)
+                                }
+                                Err(output_stream::Error::Truncated(total))
+                                    if is_close_notify_reported(&connection) =>
+                                {
+                                    connection.mark(Mark::BodyComplete);
+                                    Ok(total)
+                                }
+                                Err(e) => Err(map_err(&connection, Error::Copy(e))),
+                            })
+                        },
+                    ),
+                ),
+                Err((_, e)) => callback(Err(Error::OutputStream(e))),
+            },
+        )
+    }
+
+    /// Asynchronously save all body bytes to given [File](https://docs.gtk.org/gio/iface.File.html)
+    /// * replace existing file, if any, on success only
+    /// * the bytes are written to the temporary sibling file, deleted on failure,
+    ///   see `crate::gio::file_download` for details
+    /// * the temporary file is kept on the missing `close_notify` (`CloseNotify::Required` policy),
+    ///   and returned by `file_download::Error::Truncated`
+    /// * return bytes total on success
+    pub fn save_to_file_async(
+        self,
+        file: &File,
+        priority: Priority,
+        cancellable: Cancellable,
+        callback: impl FnOnce(Result<usize, Error>) + 'static,
+    ) {
+        let connection = self.connection.clone();
+        let file = file.clone();
+        file_download::from_input_stream_async(
+            self.into_input_stream(),
+            file.clone(),
+            ChecksumType::Md5, // not in use, the cheapest one
+            cancellable.clone(),
+            priority,
+            Size::default(),
+            (
+                |_, _| {},
+                move |result| match result {
+                    Ok((_, _, total)) => {
+                        connection.mark(Mark::BodyComplete);
+                        callback(Ok(total))
+                    }
+                    Err(file_download::Error::Truncated(temporary, total))
+                        if is_close_notify_reported(&connection) =>
+                    {
+                        file_download::commit_async(
+                            temporary.clone(),
+                            &file,
+                            priority,
+                            &cancellable,
+                            move |result| match result {
+                                Ok(()) => {
+                                    connection.mark(Mark::BodyComplete);
+                                    callback(Ok(total))
+                                }
+                                // ignore the delete result, to report the move error
+                                Err(e) => {
+                                    temporary.delete_async(priority, Cancellable::NONE, move |_| {
+                                        callback(Err(Error::File(e)))
+                                    })
+                                }
+                            },
+                        )
+                    }
+                    Err(e) => callback(Err(map_err(&connection, Error::Download(e)))),
+                },
+            ),
+        )
+    }
+
+    /// Spawn new [Subprocess](https://docs.gtk.org/gio/class.Subprocess.html) for `argv`
+    /// and stream all body bytes to its `stdin`, as they arrive
+    /// * useful to play audio or video content with the external player
+    /// * return the `Exit` status with bytes total on success
+    /// * see `crate::gio::subprocess` for details
+    pub fn pipe_to_subprocess_async(
+        self,
+        argv: &[&OsStr],
+        priority: Priority,
+        cancellable: Cancellable,
+        callback: impl FnOnce(Result<(Exit, usize), subprocess::Error>) + 'static,
+    ) {
+        let connection = self.connection.clone();
+        subprocess::from_input_stream_async(
+            self.into_input_stream(),
+            argv,
+            cancellable,
+            priority,
+            Size::default(),
+            (
+                |_, _| {},
+                move |result| {
+                    if result.is_ok() {
+                        connection.mark(Mark::BodyComplete)
+                    }
+                    callback(result)
+                },
+            ),
+        )
+    }
+
+    /// Asynchronously read first `len` body bytes (or less, on the end of stream),
+    /// then guess the content type for them
+    /// * the bytes are kept in `Self` buffer, so the content is not lost
+    /// * see `crate::gio::content_type::sniff` for details
+    pub fn sniff_async(
+        mut self,
+        declared: String,
+        len: usize,
+        priority: Priority,
+        cancellable: Cancellable,
+        callback: impl FnOnce(Result<(Sniff, Self), Error>) + 'static,
+    ) {
+        if self.buffer.len() >= len {
+            return callback(Ok((sniff(&declared, &self.buffer[..len]), self)));
+        }
+        self.connection.stream().input_stream().read_bytes_async(
+            len - self.buffer.len(),
+            priority,
+            Some(&cancellable.clone()),
+            move |result| match result {
+                Ok(bytes) => {
+                    // is end of stream
+                    if bytes.is_empty() {
+                        return callback(Ok((sniff(&declared, &self.buffer), self)));
+                    }
+                    self.buffer.extend_from_slice(&bytes);
+                    self.sniff_async(declared, len, priority, cancellable, callback)
+                }
+                Err(e) if e.matches(TlsError::Eof) => {
+                    if is_close_notify_reported(&self.connection) {
+                        callback(Ok((sniff(&declared, &self.buffer), self)))
+                    } else {
+                        callback(Err(Error::Truncated(Bytes::from_owned(self.buffer))))
+                    }
+                }
+                Err(e) => callback(Err(map_err(&self.connection, Error::InputStream(e)))),
+            },
+        )
+    }
+
+    /// Asynchronously close the `Connection`, skip the body bytes not read
+    pub fn close_async(
+        self,
+        priority: Priority,
+        cancellable: Cancellable,
+        callback: impl FnOnce(Result<(), Error>) + 'static,
+    ) {
+        self.connection
+            .stream()
+            .close_async(priority, Some(&cancellable), move |result| {
+                callback(result.map_err(Error::Close))
+            })
+    }
+
+    // Getters
+
+    /// Get body bytes received (or sniffed) before any read action
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Get max bytes total, `None` for unlimited
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Get reference to the `Connection` owned
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    // Converters
+
+    /// Convert `Self` into [InputStream](https://docs.gtk.org/gio/class.InputStream.html)
+    /// * the stream begins with the body bytes received together with the header
+    /// * the stream owns the `Connection`, so it's safe to pass it to any GIO consumer
+    /// * the stream is bounded by `Self` limit, if any
+    /// * the timeouts are reported as `IOErrorEnum::TimedOut` (body idle)
+    ///   or `IOErrorEnum::Closed` (deadline) errors
+    /// * the missing `close_notify` is reported as `TlsError::Eof` error,
+    ///   unless `CloseNotify::Ignored` policy
+    pub fn into_input_stream(self) -> InputStream {
+        let stream = Stream::new(self.connection.stream(), self.buffer);
+        match self.limit {
+            Some(limit) => BoundedInputStream::new(&stream, Some(limit)).upcast(),
+            None => stream.upcast(),
+        }
+    }
+
+    // Setters
+
+    /// Change max bytes total, `None` for unlimited
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit
+    }
+}
+
+// Tools
+
+/// Get the stricter of two optional limits, `None` for unlimited
+pub(crate) fn min(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Replace the input stream `Error` with the typed one, if the reason is timeout
+/// (by `Timeouts` of the `Connection`)
+fn map_err(connection: &Connection, e: Error) -> Error {
+    let input_stream = match e {
+        Error::InputStream(ref e)
+        | Error::Copy(output_stream::Error::InputStream(_, ref e))
+        | Error::Download(file_download::Error::OutputStream(output_stream::Error::InputStream(
+            _,
+            ref e,
+        ))) => e,
+        e => return e,
+    };
+    if connection.is_deadline_expired() {
+        Error::Timeout(Phase::Deadline)
+    } else if input_stream.matches(IOErrorEnum::TimedOut) {
+        Error::Timeout(Phase::BodyIdle)
+    } else {
+        e
+    }
+}
+
+/// Check the missing `close_notify` is allowed by `CloseNotify::Reported` policy
+/// * mark the `Connection` to notify the user
+fn is_close_notify_reported(connection: &Connection) -> bool {
+    if connection.tls_policy().close_notify == CloseNotify::Reported {
+        connection.set_close_notify_missing();
+        return true;
+    }
+    false
+}
+
+/// Asynchronously read all bytes from `connection` into `buffer`
+fn read_to_vec_async(
+    mut buffer: Vec<u8>,
+    connection: Connection,
+    limit: Option<usize>,
+    priority: Priority,
+    cancellable: Cancellable,
+    callback: impl FnOnce(Result<Vec<u8>, Error>) + 'static,
+) {
+    connection.stream().input_stream().read_bytes_async(
+        CHUNK,
+        priority,
+        Some(&cancellable.clone()),
+        move |result| match result {
+            Ok(bytes) => {
+                // is end of stream
+                if bytes.is_empty() {
+                    return callback(Ok(buffer));
+                }
+                buffer.extend_from_slice(&bytes);
+
+                // prevent memory overflow
+                if let Some(limit) = limit
+                    && buffer.len() > limit
+                {
+                    return callback(Err(Error::BytesTotal(buffer.len(), limit)));
+                }
+
+                // handle next chunk..
+                read_to_vec_async(buffer, connection, limit, priority, cancellable, callback)
+            }
+            Err(e) if e.matches(TlsError::Eof) => {
+                if is_close_notify_reported(&connection) {
+                    callback(Ok(buffer))
+                } else {
+                    callback(Err(Error::Truncated(Bytes::from_owned(buffer))))
+                }
+            }
+            Err(e) => callback(Err(Error::InputStream(e))),
+        },
+    )
+}
+
+#[test]
+fn test() {
+    assert_eq!(min(None, None), None);
+    assert_eq!(min(Some(1), None), Some(1));
+    assert_eq!(min(None, Some(2)), Some(2));
+    assert_eq!(min(Some(3), Some(2)), Some(2));
+}
@@ -0,0 +1,54 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug)]
+pub enum Error {
+    BytesTotal(usize, usize),
+    Close(glib::Error),
+    Copy(crate::gio::output_stream::Error),
+    Download(crate::gio::file_download::Error),
+    File(glib::Error),
+    InputStream(glib::Error),
+    OutputStream(glib::Error),
+    Timeout(crate::client::timeouts::Phase),
+    /// All bytes received, when the server closed the connection without TLS `close_notify`
+    /// (possibly truncated)
+    Truncated(glib::Bytes),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::BytesTotal(total, limit) => {
+                write!(f, "Bytes total limit reached: {total} / {limit}")
+            }
+            Self::Close(e) => {
+                write!(f, "Close error: {e}")
+            }
+            Self::Copy(e) => {
+                write!(f, "Copy error: {e}")
+            }
+            Self::Download(e) => {
+                write!(f, "Download error: {e}")
+            }
+            Self::File(e) => {
+                write!(f, "File error: {e}")
+            }
+            Self::InputStream(e) => {
+                write!(f, "Input stream error: {e}")
+            }
+            Self::OutputStream(e) => {
+                write!(f, "Output stream error: {e}")
+            }
+            Self::Timeout(phase) => {
+                write!(f, "Timeout on {phase} phase")
+            }
+            Self::Truncated(bytes) => {
+                write!(
+                    f,
+                    "Possibly truncated after {} bytes: missing TLS close_notify",
+                    bytes.len()
+                )
+            }
+        }
+    }
+}
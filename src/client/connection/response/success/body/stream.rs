@@ -0,0 +1,83 @@
+mod imp;
+
+use gio::IOStream;
+use glib::{Object, subclass::prelude::ObjectSubclassIsExt};
+
+glib::wrapper! {
+    /// [InputStream](https://docs.gtk.org/gio/class.InputStream.html) implementation for `Body`
+    /// * read the `buffer` bytes first, then continue with the `IOStream` bytes
+    /// * owns the `IOStream` reference to keep `Connection` active
+    pub struct Stream(ObjectSubclass<imp::Stream>) @extends gio::InputStream;
+}
+
+impl Stream {
+    // Constructors
+
+    /// Create new `Self` for given `IOStream` and body bytes received together with the header
+    pub fn new(io_stream: IOStream, buffer: Vec<u8>) -> Self {
+        let this: Self = Object::new();
+        this.imp().init(io_stream, buffer);
+        this
+    }
+}
+
+#[test]
+fn test() {
+    use gio::{MemoryInputStream, MemoryOutputStream, SimpleIOStream, prelude::InputStreamExt};
+    use glib::{Bytes, object::Cast};
+
+    let s = Stream::new(
+        SimpleIOStream::new(
+            &MemoryInputStream::from_bytes(&Bytes::from(b"dy")),
+            &MemoryOutputStream::new_resizable(),
+        )
+        .upcast(),
+        b"bo".to_vec(),
+    );
+    let b = s.read_bytes(1, gio::Cancellable::NONE).unwrap();
+    assert_eq!(&*b, b"b");
+    let b = s.read_bytes(0x10000, gio::Cancellable::NONE).unwrap();
+    assert_eq!(&*b, b"o");
+    let b = s.read_bytes(0x10000, gio::Cancellable::NONE).unwrap();
+    assert_eq!(&*b, b"dy");
+    assert!(
+        s.read_bytes(0x10000, gio::Cancellable::NONE)
+            .unwrap()
+            .is_empty()
+    );
+    assert!(s.close(gio::Cancellable::NONE).is_ok());
+
+    // async methods are not threaded
+    use std::{cell::RefCell, rc::Rc};
+    let s = Stream::new(
+        SimpleIOStream::new(
+            &MemoryInputStream::from_bytes(&Bytes::from(b"dy")),
+            &MemoryOutputStream::new_resizable(),
+        )
+        .upcast(),
+        b"bo".to_vec(),
+    );
+    let result = Rc::new(RefCell::new(Vec::new()));
+    fn read(s: Stream, result: Rc<RefCell<Vec<u8>>>) {
+        s.clone().read_bytes_async(
+            0x10000,
+            glib::Priority::DEFAULT,
+            gio::Cancellable::NONE,
+            move |bytes| {
+                let bytes = bytes.unwrap();
+                if bytes.is_empty() {
+                    return s.close_async(glib::Priority::DEFAULT, gio::Cancellable::NONE, |r| {
+                        assert!(r.is_ok())
+                    });
+                }
+                result.borrow_mut().extend_from_slice(&bytes);
+                read(s, result)
+            },
+        )
+    }
+    read(s.clone(), result.clone());
+    while !s.is_closed() {
+        glib::MainContext::default().iteration(true);
+    }
+    assert_eq!(&*result.borrow(), b"body");
+}
@@ -0,0 +1,102 @@
+use crate::gio::async_input_stream::{ReadAsync, Source, override_async};
+use gio::{
+    Cancellable, IOStream,
+    prelude::{IOStreamExt, InputStreamExt, InputStreamExtManual},
+    subclass::prelude::*,
+};
+use glib::{Bytes, Priority};
+use std::sync::Mutex;
+
+#[derive(Default)]
+pub struct Stream {
+    state: Mutex<Option<(Source<IOStream>, Vec<u8>)>>,
+}
+
+impl Stream {
+    pub fn init(&self, io_stream: IOStream, buffer: Vec<u8>) {
+        self.state
+            .lock()
+            .unwrap()
+            .replace((Source(io_stream), buffer));
+    }
+}
+
+#[glib::object_subclass]
+impl ObjectSubclass for Stream {
+    const NAME: &'static str = "GGeminiBodyStream";
+    type Type = super::Stream;
+    type ParentType = gio::InputStream;
+
+    fn class_init(class: &mut Self::Class) {
+        override_async::<Self>(class)
+    }
+}
+
+impl ObjectImpl for Stream {}
+
+impl InputStreamImpl for Stream {
+    fn read(
+        &self,
+        buffer: &mut [u8],
+        cancellable: Option<&Cancellable>,
+    ) -> Result<usize, glib::Error> {
+        let mut state = self.state.lock().unwrap();
+        match state.as_mut() {
+            Some((source, pending)) => {
+                // read the bytes received together with the header first
+                if !pending.is_empty() {
+                    let len = buffer.len().min(pending.len());
+                    buffer[..len].copy_from_slice(&pending[..len]);
+                    pending.drain(..len);
+                    return Ok(len);
+                }
+                source.0.input_stream().read(buffer, cancellable)
+            }
+            None => Ok(0),
+        }
+    }
+
+    fn close(&self, cancellable: Option<&Cancellable>) -> Result<(), glib::Error> {
+        match self.state.lock().unwrap().take() {
+            Some((source, _)) => source.0.close(cancellable),
+            None => Ok(()),
+        }
+    }
+}
+
+impl ReadAsync for Stream {
+    fn read_async(
+        &self,
+        len: usize,
+        priority: Priority,
+        cancellable: Option<&Cancellable>,
+        callback: impl FnOnce(Result<Bytes, glib::Error>) + 'static,
+    ) {
+        let input_stream = match self.state.lock().unwrap().as_mut() {
+            Some((source, pending)) => {
+                // read the bytes received together with the header first
+                if !pending.is_empty() {
+                    let len = len.min(pending.len());
+                    return callback(Ok(Bytes::from_owned(
+                        pending.drain(..len).collect::<Vec<u8>>(),
+                    )));
+                }
+                source.0.input_stream()
+            }
+            None => return callback(Ok(Bytes::from_static(&[]))),
+        };
+        input_stream.read_bytes_async(len, priority, cancellable, callback)
+    }
+
+    fn close_async(
+        &self,
+        priority: Priority,
+        cancellable: Option<&Cancellable>,
+        callback: impl FnOnce(Result<(), glib::Error>) + 'static,
+    ) {
+        match self.state.lock().unwrap().take() {
+            Some((source, _)) => source.0.close_async(priority, cancellable, callback),
+            None => callback(Ok(())),
+        }
+    }
+}
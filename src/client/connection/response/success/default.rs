@@ -36,6 +36,21 @@ impl Default {
             header,
         })
     }
+
+    // Converters
+
+    /// Convert `Self` into the `Header` and `Body` owning given `Connection`
+    /// * the `content` bytes become the beginning of the `Body`
+    /// * the `Body` limit is resolved by `Connection` size limits policy for the MIME type
+    pub fn into_body(self, connection: crate::client::Connection) -> (Header, super::Body) {
+        let limit = connection
+            .size_limits
+            .limit(&self.header.mime().unwrap_or_default());
+        (
+            self.header,
+            super::Body::new(connection, self.content, limit),
+        )
+    }
 }
 
 #[test]
@@ -36,6 +36,13 @@ impl Header {
         .map_or(Err(Error::Mime), |s| Ok(s.to_lowercase()))
     }
 
+    /// Guess content type for the first body `data` bytes,
+    /// report it together with the MIME type parsed
+    /// * see `crate::gio::content_type::sniff` for details
+    pub fn sniff(&self, data: &[u8]) -> Result<crate::gio::content_type::Sniff, Error> {
+        Ok(crate::gio::content_type::sniff(&self.mime()?, data))
+    }
+
     /// Get header bytes of `Self`
     pub fn as_bytes(&self) -> &[u8] {
         self.0.as_bytes()
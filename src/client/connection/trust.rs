@@ -0,0 +1,91 @@
+use crate::client::{
+    KnownHosts,
+    pinning::Pinning,
+    validation::{self, Validation, Verification},
+};
+use gio::{
+    NetworkAddress, TlsCertificate, TlsClientConnection,
+    prelude::{NetworkAddressExt, TlsConnectionExt},
+};
+use glib::object::Cast;
+use std::cell::{Cell, RefCell};
+
+/// Server certificate trust state, shared with the `accept-certificate` handler
+#[derive(Debug, Default)]
+pub struct Trust {
+    known_hosts: RefCell<Option<KnownHosts>>,
+    pinning: RefCell<Pinning>,
+    validation: Cell<Validation>,
+    verification: Cell<Option<Verification>>,
+}
+
+impl Trust {
+    // Actions
+
+    /// Verify server `certificate` for `identity` by the `Validation` mode,
+    /// record the `Verification` result
+    /// * `pinned` is `None` to accept any certificate by TOFU (the first visit)
+    /// * reject the certificate already rejected by `KnownHosts` database, if set
+    pub fn accept(
+        &self,
+        tls_client_connection: &TlsClientConnection,
+        identity: Option<&NetworkAddress>,
+        pinned: Option<&[TlsCertificate]>,
+        certificate: &TlsCertificate,
+    ) -> bool {
+        let host = identity
+            .map(|a| a.hostname().to_lowercase())
+            .unwrap_or_default();
+        let verification = match *self.known_hosts.borrow() {
+            Some(ref known_hosts) => match known_hosts.verification(tls_client_connection) {
+                Some(verification) => verification,
+                None => return false,
+            },
+            None => validation::verify(
+                self.validation.get(),
+                &self.pinning.borrow(),
+                &host,
+                identity.map(|a| a.upcast_ref()),
+                pinned,
+                certificate,
+            ),
+        };
+        self.verification.set(Some(verification));
+        verification.is_accepted && self.known_hosts.borrow().is_none()
+    }
+
+    // Getters
+
+    /// Get `KnownHosts` database, if set
+    pub fn known_hosts(&self) -> Option<KnownHosts> {
+        self.known_hosts.borrow().clone()
+    }
+
+    /// Get `Verification` result of the last handshake, if any
+    /// * the certificate accepted by `KnownHosts` is not handled by `Self`,
+    ///   see `KnownHosts::verification`
+    pub fn verification(&self) -> Option<Verification> {
+        self.verification.get()
+    }
+
+    // Setters
+
+    /// Verify server certificates by `KnownHosts` database,
+    /// instead of `Self` pinning and validation settings
+    pub fn set_known_hosts(
+        &self,
+        known_hosts: Option<&KnownHosts>,
+        tls_connection: &impl TlsConnectionExt,
+    ) {
+        tls_connection.set_database(known_hosts);
+        self.known_hosts.replace(known_hosts.cloned());
+    }
+
+    pub fn set_pinning(&self, pinning: Pinning) {
+        self.pinning.replace(pinning);
+    }
+
+    pub fn set_validation(&self, validation: Validation) {
+        self.validation.set(validation)
+    }
+}
@@ -0,0 +1,323 @@
+//! Download manager for the [Success](https://geminiprotocol.net/docs/protocol-specification.gmi#success)
+//! response content, built on `Client` and `crate::gio::file_download`
+
+pub mod error;
+pub mod file_name;
+pub mod job;
+
+pub use error::Error;
+pub use job::{Job, State};
+
+use super::{
+    Client,
+    connection::{Mode, Request, Response, response::success::Body},
+};
+use crate::gio::{file_download, output_stream::Size};
+use gio::{
+    File, TlsCertificate,
+    prelude::{CancellableExt, FileExt},
+};
+use glib::{ChecksumType, Priority, Uri};
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    rc::Rc,
+};
+
+// Defaults
+
+pub const DEFAULT_CONCURRENCY: usize = 3;
+
+/// Queue the download jobs, and run them up to `concurrency` at once
+/// * every job has own `State` and `Cancellable`
+/// * `on_update` is callback for every `Job` state (or progress) change
+pub struct Manager {
+    client: Client,
+    concurrency: usize,
+    jobs: RefCell<Vec<Rc<Job>>>,
+    limit: Cell<Option<usize>>,
+    on_update: Box<dyn Fn(&Job)>,
+    priority: Priority,
+    queue: RefCell<VecDeque<Rc<Job>>>,
+    running: Cell<usize>,
+}
+
+impl Manager {
+    // Constructors
+
+    /// Create new `Self` for given `Client`
+    /// * `concurrency` is max jobs running at once (`DEFAULT_CONCURRENCY` recommended)
+    pub fn new(client: Client, concurrency: usize, on_update: impl Fn(&Job) + 'static) -> Rc<Self> {
+        Rc::new(Self {
+            client,
+            concurrency: concurrency.max(1),
+            jobs: RefCell::new(Vec::new()),
+            limit: Cell::new(None),
+            on_update: Box::new(on_update),
+            priority: Priority::DEFAULT,
+            queue: RefCell::new(VecDeque::new()),
+            running: Cell::new(0),
+        })
+    }
+
+    // Actions
+
+    /// Queue new download job for given `uri`
+    /// * the target file name would be auto-generated in the `directory`,
+    ///   using `uri` path and MIME type returned by the server
+    pub fn enqueue(
+        self: &Rc<Self>,
+        uri: Uri,
+        directory: File,
+        client_certificate: Option<TlsCertificate>,
+        server_certificates: Option<Vec<TlsCertificate>>,
+    ) -> Rc<Job> {
+        let job = Rc::new(Job::new(
+            self.jobs.borrow().len(),
+            uri,
+            directory,
+            client_certificate,
+            server_certificates,
+        ));
+        self.jobs.borrow_mut().push(job.clone());
+        self.queue.borrow_mut().push_back(job.clone());
+        (self.on_update)(&job);
+        self.next();
+        job
+    }
+
+    /// Cancel the job by `id`
+    /// * return `false` if the job not found or already completed
+    pub fn cancel(&self, id: usize) -> bool {
+        let job = match self.job(id) {
+            Some(job) => job,
+            None => return false,
+        };
+        if job.state().is_complete() {
+            return false;
+        }
+        job.cancellable.cancel();
+        // queued job is not running yet, complete it here
+        let mut queue = self.queue.borrow_mut();
+        if let Some(i) = queue.iter().position(|j| j.id == id) {
+            queue.remove(i);
+            drop(queue);
+            job.set_state(State::Cancelled);
+            (self.on_update)(&job);
+        }
+        true
+    }
+
+    // Getters
+
+    /// Get `Job` by `id`
+    pub fn job(&self, id: usize) -> Option<Rc<Job>> {
+        self.jobs.borrow().get(id).cloned()
+    }
+
+    /// Get all jobs, in order of queue
+    pub fn jobs(&self) -> Vec<Rc<Job>> {
+        self.jobs.borrow().clone()
+    }
+
+    /// Get reference to `Client` in use
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    // Setters
+
+    /// Set max bytes for every next job, `None` for unlimited (by default)
+    pub fn set_limit(&self, limit: Option<usize>) {
+        self.limit.replace(limit);
+    }
+
+    // Tools
+
+    /// Run next queued job, if the `concurrency` limit allows it
+    fn next(self: &Rc<Self>) {
+        while self.running.get() < self.concurrency {
+            let job = match self.queue.borrow_mut().pop_front() {
+                Some(job) => job,
+                None => return,
+            };
+            self.running.replace(self.running.get() + 1);
+            self.run(job);
+        }
+    }
+
+    /// Complete the `job` with `state` given, then run next one
+    /// * delete the empty target file reserved, if the job is not completed successfully
+    /// * delete the temporary file of `file_download::Error::Truncated`, if any
+    fn complete(self: &Rc<Self>, job: &Rc<Job>, state: State) {
+        let mut files = Vec::new();
+        if let Some(file) = job.file()
+            && !matches!(state, State::Completed { .. })
+        {
+            files.push(file)
+        }
+        if let State::Failed(Error::Download(file_download::Error::Truncated(ref temporary, _))) =
+            state
+        {
+            files.push(temporary.clone())
+        }
+        let this = self.clone();
+        let job = job.clone();
+        delete_async(files, self.priority, move || {
+            job.set_state(if job.cancellable.is_cancelled() {
+                State::Cancelled
+            } else {
+                state
+            });
+            (this.on_update)(&job);
+            this.running.replace(this.running.get() - 1);
+            this.next();
+        })
+    }
+
+    /// Complete the `job` with unexpected response `header`
+    fn fail(self: &Rc<Self>, job: &Rc<Job>, header: &str) {
+        self.complete(job, State::Failed(Error::Response(header.to_string())))
+    }
+
+    /// Receive the `body` of `mime` type to the `file` reserved
+    fn receive(self: &Rc<Self>, job: Rc<Job>, body: Body, mime: String, file: File) {
+        job.set_state(State::Receiving {
+            mime,
+            file: file.clone(),
+            total: 0,
+            rate: 0.0,
+        });
+        (self.on_update)(&job);
+
+        let started = glib::monotonic_time();
+        let this = self.clone();
+        file_download::from_input_stream_async(
+            body.into_input_stream(),
+            file,
+            ChecksumType::Sha256,
+            job.cancellable.clone(),
+            self.priority,
+            Size {
+                limit: self.limit.get(),
+                ..Size::default()
+            },
+            (
+                {
+                    let this = this.clone();
+                    let job = job.clone();
+                    move |_, total| {
+                        job.set_progress(total, started);
+                        (this.on_update)(&job)
+                    }
+                },
+                move |result| {
+                    this.complete(
+                        &job,
+                        match result {
+                            Ok((file, checksum, total)) => State::Completed {
+                                file,
+                                checksum,
+                                total,
+                            },
+                            Err(e) => State::Failed(Error::Download(e)),
+                        },
+                    )
+                },
+            ),
+        )
+    }
+
+    fn run(self: &Rc<Self>, job: Rc<Job>) {
+        job.set_state(State::Connecting);
+        (self.on_update)(&job);
+
+        let this = self.clone();
+        self.client.request_async(
+            Request::Gemini {
+                uri: job.uri.clone(),
+                mode: Mode::HeaderOnly,
+            },
+            self.priority,
+            job.cancellable.clone(),
+            job.client_certificate.clone(),
+            job.server_certificates.clone(),
+            move |result| match result {
+                Ok((response, connection)) => match response {
+                    Response::Success(success) => {
+                        let mime = match success.mime() {
+                            Ok(mime) => mime,
+                            Err(e) => return this.complete(&job, State::Failed(Error::Mime(e))),
+                        };
+                        let (_, body) = success.into_body(connection);
+                        file_name::unique_async(
+                            job.directory.clone(),
+                            file_name::from_uri(&job.uri, &mime),
+                            this.priority,
+                            Some(job.cancellable.clone()),
+                            move |result| match result {
+                                Ok(file) => this.receive(job, body, mime, file),
+                                Err(e) => this.complete(&job, State::Failed(Error::Reserve(e))),
+                            },
+                        )
+                    }
+                    Response::Input(input) => this.fail(&job, input.as_str()),
+                    Response::Redirect(redirect) => this.fail(&job, redirect.as_str()),
+                    Response::Failure(failure) => this.fail(&job, failure.as_str()),
+                    Response::Certificate(certificate) => this.fail(&job, certificate.as_str()),
+                },
+                Err(e) => this.complete(&job, State::Failed(Error::Client(Box::new(e)))),
+            },
+        )
+    }
+}
+
+/// Asynchronously delete `files`, ignore the errors, then run `callback`
+fn delete_async(mut files: Vec<File>, priority: Priority, callback: impl FnOnce() + 'static) {
+    match files.pop() {
+        Some(file) => file.delete_async(priority, gio::Cancellable::NONE, move |_| {
+            delete_async(files, priority, callback)
+        }),
+        None => callback(),
+    }
+}
+
+#[test]
+fn test() {
+    use gio::{SocketListener, prelude::SocketListenerExt};
+
+    // local server, that closes the connection at once
+    let listener = SocketListener::new();
+    let port = listener.add_any_inet_port(gio::Cancellable::NONE).unwrap();
+    listener.accept_async(gio::Cancellable::NONE, drop);
+
+    let manager = Manager::new(Client::new(), 1, |_| ());
+    let directory = File::for_path(glib::tmp_dir());
+    let uri = || {
+        Uri::parse(
+            &format!("gemini://127.0.0.1:{port}/file"),
+            glib::UriFlags::NONE,
+        )
+        .unwrap()
+    };
+
+    let a = manager.enqueue(uri(), directory.clone(), None, None);
+    let b = manager.enqueue(uri(), directory, None, None);
+    assert!(matches!(*a.state(), State::Connecting));
+    assert!(matches!(*b.state(), State::Queued));
+
+    // queued job is cancelled at once
+    assert!(manager.cancel(b.id));
+    assert!(matches!(*b.state(), State::Cancelled));
+    assert!(!manager.cancel(b.id));
+
+    // running job fails on connection closed, and does not leave the target file
+    let context = glib::MainContext::default();
+    while !a.state().is_complete() {
+        context.iteration(true);
+    }
+    assert!(matches!(*a.state(), State::Failed(Error::Client(_))));
+    assert!(a.file().is_none());
+    assert_eq!(manager.jobs().len(), 2);
+    assert!(manager.job(2).is_none());
+}
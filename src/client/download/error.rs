@@ -0,0 +1,34 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug)]
+pub enum Error {
+    Client(Box<crate::client::Error>),
+    Download(crate::gio::file_download::Error),
+    Mime(crate::client::connection::response::success::Error),
+    /// Could not create the target file
+    Reserve(glib::Error),
+    /// Unexpected (not success) response header
+    Response(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Client(e) => {
+                write!(f, "Client error: {e}")
+            }
+            Self::Download(e) => {
+                write!(f, "Download error: {e}")
+            }
+            Self::Mime(e) => {
+                write!(f, "MIME error: {e}")
+            }
+            Self::Reserve(e) => {
+                write!(f, "Could not create target file: {e}")
+            }
+            Self::Response(header) => {
+                write!(f, "Unexpected response: {}", header.trim())
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! File name tools for the downloads
+
+use gio::{Cancellable, File, FileCreateFlags, IOErrorEnum, prelude::FileExt};
+use glib::{Priority, Uri};
+
+/// Default file name, when the `Uri` path does not provide any
+pub const DEFAULT_NAME: &str = "index";
+
+/// Suggest file extension for given MIME type
+/// * use [content type](https://docs.gtk.org/gio/content-types.html) API to resolve aliases
+pub fn extension(mime: &str) -> Option<&'static str> {
+    fn known(mime: &str) -> Option<&'static str> {
+        Some(match mime {
+            "application/gzip" => "gz",
+            "application/json" => "json",
+            "application/pdf" => "pdf",
+            "application/xml" => "xml",
+            "application/zip" => "zip",
+            "audio/flac" => "flac",
+            "audio/mpeg" => "mp3",
+            "audio/ogg" => "ogg",
+            "audio/wav" | "audio/x-wav" => "wav",
+            "image/gif" => "gif",
+            "image/jpeg" => "jpg",
+            "image/png" => "png",
+            "image/svg+xml" => "svg",
+            "image/webp" => "webp",
+            "text/css" => "css",
+            "text/csv" => "csv",
+            "text/gemini" => "gmi",
+            "text/html" => "html",
+            "text/markdown" => "md",
+            "text/plain" => "txt",
+            "video/mp4" => "mp4",
+            "video/webm" => "webm",
+            _ => return None,
+        })
+    }
+    let mime = mime.trim().to_lowercase();
+    known(&mime).or_else(|| {
+        let content_type = gio::content_type_from_mime_type(&mime)?;
+        known(&gio::content_type_get_mime_type(&content_type)?).or_else(|| {
+            if gio::content_type_is_a(&content_type, "text/plain") {
+                Some("txt")
+            } else {
+                None
+            }
+        })
+    })
+}
+
+/// Build file name for `uri` of `mime` type
+/// * append the extension suggested, if the `uri` path does not include any
+pub fn from_uri(uri: &Uri, mime: &str) -> String {
+    let path = uri.path();
+    let name = path
+        .rsplit('/')
+        .next()
+        .and_then(|s| Uri::unescape_string(s, None::<&str>))
+        .map(|s| s.replace(['/', '\\', '\0'], "_"))
+        .map(|s| s.trim_start_matches('.').trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| DEFAULT_NAME.to_string());
+    match extension(mime) {
+        Some(extension) if !name.contains('.') => format!("{name}.{extension}"),
+        _ => name,
+    }
+}
+
+/// Asynchronously create unique empty `File` for `name` in `directory`
+/// * add ` (n)` postfix on name collision with existing file
+/// * the file is created atomically (without `FileCreateFlags::REPLACE_DESTINATION`),
+///   so it stays reserved for the caller (e.g. another download can not take the same name)
+pub fn unique_async(
+    directory: File,
+    name: String,
+    priority: Priority,
+    cancellable: Option<Cancellable>,
+    callback: impl FnOnce(Result<File, glib::Error>) + 'static,
+) {
+    create_async(directory, name, 0, priority, cancellable, callback)
+}
+
+// Tools
+
+/// Try to create the file `name` with ` (n)` postfix, increase `n` on collision
+fn create_async(
+    directory: File,
+    name: String,
+    n: usize,
+    priority: Priority,
+    cancellable: Option<Cancellable>,
+    callback: impl FnOnce(Result<File, glib::Error>) + 'static,
+) {
+    let file = directory.child(if n == 0 {
+        name.clone()
+    } else {
+        match name.rsplit_once('.') {
+            Some((stem, extension)) if !stem.is_empty() => format!("{stem} ({n}).{extension}"),
+            _ => format!("{name} ({n})"),
+        }
+    });
+    file.clone().create_async(
+        FileCreateFlags::NONE,
+        priority,
+        cancellable.clone().as_ref(),
+        move |result| match result {
+            Ok(_) => callback(Ok(file)),
+            Err(e) if e.matches(IOErrorEnum::Exists) => {
+                create_async(directory, name, n + 1, priority, cancellable, callback)
+            }
+            Err(e) => callback(Err(e)),
+        },
+    )
+}
+
+#[test]
+fn test() {
+    use glib::UriFlags;
+
+    fn t(uri: &str, mime: &str, name: &str) {
+        assert_eq!(
+            from_uri(&Uri::parse(uri, UriFlags::NONE).unwrap(), mime),
+            name
+        )
+    }
+    t("gemini://geminiprotocol.net/", "text/gemini", "index.gmi");
+    t("gemini://geminiprotocol.net", "text/gemini", "index.gmi");
+    t("gemini://geminiprotocol.net/file", "image/png", "file.png");
+    t(
+        "gemini://geminiprotocol.net/file.jpeg",
+        "image/jpeg",
+        "file.jpeg",
+    );
+    t("gemini://geminiprotocol.net/a%20b", "text/plain", "a b.txt");
+    t(
+        "gemini://geminiprotocol.net/.hidden",
+        "text/plain",
+        "hidden.txt",
+    );
+    t(
+        "gemini://geminiprotocol.net/file",
+        "application/x-unknown-type",
+        "file",
+    );
+
+    let directory = File::for_path(glib::tmp_dir());
+    let name = format!("ggemini-{:08x}.txt", glib::random_int());
+    let unique = |name: &str| {
+        let result = std::rc::Rc::new(std::cell::RefCell::new(None));
+        unique_async(
+            directory.clone(),
+            name.to_string(),
+            Priority::DEFAULT,
+            None,
+            {
+                let result = result.clone();
+                move |r| {
+                    result.replace(Some(r));
+                }
+            },
+        );
+        let context = glib::MainContext::default();
+        while result.borrow().is_none() {
+            context.iteration(true);
+        }
+        result.take().unwrap().unwrap()
+    };
+    let a = unique(&name);
+    assert_eq!(a.basename().unwrap(), std::path::Path::new(&name));
+    let b = unique(&name);
+    assert!(
+        b.basename()
+            .unwrap()
+            .to_string_lossy()
+            .ends_with(" (1).txt")
+    );
+    a.delete(gio::Cancellable::NONE).unwrap();
+    b.delete(gio::Cancellable::NONE).unwrap();
+}
@@ -0,0 +1,80 @@
+pub mod state;
+pub use state::State;
+
+use gio::{Cancellable, File, TlsCertificate};
+use glib::Uri;
+use std::cell::{Ref, RefCell};
+
+/// Single download job of the `Manager`
+pub struct Job {
+    pub id: usize,
+    pub uri: Uri,
+    pub(super) cancellable: Cancellable,
+    pub(super) client_certificate: Option<TlsCertificate>,
+    pub(super) directory: File,
+    pub(super) server_certificates: Option<Vec<TlsCertificate>>,
+    state: RefCell<State>,
+}
+
+impl Job {
+    // Constructors
+
+    /// Create new `Self` in `State::Queued`
+    pub fn new(
+        id: usize,
+        uri: Uri,
+        directory: File,
+        client_certificate: Option<TlsCertificate>,
+        server_certificates: Option<Vec<TlsCertificate>>,
+    ) -> Self {
+        Self {
+            id,
+            uri,
+            cancellable: Cancellable::new(),
+            client_certificate,
+            directory,
+            server_certificates,
+            state: RefCell::new(State::Queued),
+        }
+    }
+
+    // Getters
+
+    /// Get current `State` of `Self`
+    pub fn state(&self) -> Ref<'_, State> {
+        self.state.borrow()
+    }
+
+    /// Get target `File`, if already known
+    pub fn file(&self) -> Option<File> {
+        match *self.state.borrow() {
+            State::Receiving { ref file, .. } | State::Completed { ref file, .. } => {
+                Some(file.clone())
+            }
+            _ => None,
+        }
+    }
+
+    // Setters
+
+    pub(super) fn set_state(&self, state: State) {
+        self.state.replace(state);
+    }
+
+    /// Update bytes `total` received, with transfer rate since `started` time
+    /// * `started` is the [monotonic](https://docs.gtk.org/glib/func.get_monotonic_time.html) time in microseconds
+    pub(super) fn set_progress(&self, bytes: usize, started: i64) {
+        if let State::Receiving {
+            ref mut total,
+            ref mut rate,
+            ..
+        } = *self.state.borrow_mut()
+        {
+            *total = bytes;
+            let elapsed = glib::monotonic_time() - started;
+            if elapsed > 0 {
+                *rate = bytes as f64 * 1_000_000.0 / elapsed as f64;
+            }
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use gio::File;
+
+/// `Job` state
+pub enum State {
+    /// Waiting for the free slot
+    Queued,
+    /// Sending request, waiting for response header
+    Connecting,
+    /// Receiving body bytes into the temporary file
+    Receiving {
+        mime: String,
+        /// Target file
+        file: File,
+        /// Bytes received
+        total: usize,
+        /// Transfer rate (bytes per second)
+        rate: f64,
+    },
+    /// Downloaded successfully
+    Completed {
+        file: File,
+        /// SHA-256 hex string
+        checksum: String,
+        total: usize,
+    },
+    Failed(super::super::Error),
+    Cancelled,
+}
+
+impl State {
+    /// Check `Self` is final state (`Completed`, `Failed` or `Cancelled`)
+    pub fn is_complete(&self) -> bool {
+        matches!(
+            self,
+            Self::Completed { .. } | Self::Failed(_) | Self::Cancelled
+        )
+    }
+}
@@ -2,30 +2,69 @@ use std::fmt::{Display, Formatter, Result};
 
 #[derive(Debug)]
 pub enum Error {
-    Connect(gio::NetworkAddress, glib::Error),
-    Connection(gio::SocketConnection, crate::client::connection::Error),
+    Connect(gio::NetworkAddress, glib::Error, crate::client::Timing),
+    Connection(
+        gio::SocketConnection,
+        crate::client::connection::Error,
+        crate::client::Timing,
+    ),
+    /// Request refused by `IdentityGuard`
+    Identity(crate::client::identity_guard::Violation),
     NetworkAddress(crate::client::connection::request::Error),
     Request(
         crate::client::connection::Connection,
         crate::client::connection::Error,
     ),
+    Timeout(
+        gio::NetworkAddress,
+        crate::client::timeouts::Phase,
+        crate::client::Timing,
+    ),
+}
+
+impl Error {
+    /// Get expired timeout `Phase`, if the request failed by timeout
+    pub fn timeout(&self) -> Option<crate::client::timeouts::Phase> {
+        match self {
+            Self::Timeout(_, phase, _)
+            | Self::Request(_, crate::client::connection::Error::Timeout(phase)) => Some(*phase),
+            _ => None,
+        }
+    }
+
+    /// Get timing breakdown collected before the failure, if any
+    pub fn timing(&self) -> Option<crate::client::Timing> {
+        match self {
+            Self::Connect(.., timing)
+            | Self::Connection(.., timing)
+            | Self::Timeout(.., timing) => Some(*timing),
+            Self::Request(connection, _) => Some(connection.timing()),
+            Self::Identity(_) | Self::NetworkAddress(_) => None,
+        }
+    }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter) -> Result {
         match self {
-            Self::Connect(_, e) => {
+            Self::Connect(_, e, _) => {
                 write!(f, "Connect error: {e}")
             }
-            Self::Connection(_, e) => {
+            Self::Connection(_, e, _) => {
                 write!(f, "Connection init error: {e}")
             }
+            Self::Identity(e) => {
+                write!(f, "Identity guard error: {e}")
+            }
             Self::NetworkAddress(e) => {
                 write!(f, "Network address error: {e}")
             }
             Self::Request(_, e) => {
                 write!(f, "Connection error: {e}")
             }
+            Self::Timeout(_, phase, _) => {
+                write!(f, "Timeout on {phase} phase")
+            }
         }
     }
 }
@@ -0,0 +1,59 @@
+//! MIME type handlers for [Success](https://geminiprotocol.net/docs/protocol-specification.gmi#success) responses
+
+pub mod content;
+pub mod error;
+pub mod need;
+pub mod registry;
+
+pub use content::Content;
+pub use error::Error;
+pub use need::Need;
+pub use registry::Registry;
+
+use super::connection::{
+    Connection,
+    response::{Success, success::body, success::default::Header},
+};
+use gio::Cancellable;
+use glib::Priority;
+use std::rc::Rc;
+
+/// Handler for the content of some MIME type
+/// * register implementation in `Registry` by MIME pattern
+pub trait Handler {
+    /// Declare the content type expected by `handle` method
+    fn need(&self) -> Need;
+
+    /// Handle parsed `header` and the body `content`, according to `Need` declared
+    /// * `Err` on body read failure
+    fn handle(&self, header: Header, content: Result<Content, Error>);
+}
+
+/// Dispatch `Success` response to the `handler` (e.g. found by `Registry::resolve`)
+/// * the `handler` receives body content according to `Need` declared
+pub fn dispatch(
+    handler: Rc<dyn Handler>,
+    success: Success,
+    connection: Connection,
+    priority: Priority,
+    cancellable: Cancellable,
+) {
+    let (header, mut body) = success.into_body(connection);
+    match handler.need() {
+        Need::Buffered(limit) => {
+            body.read_to_bytes_async(limit, priority, cancellable, move |result| {
+                handler.handle(header, result.map(Content::Buffered).map_err(Error::Body))
+            })
+        }
+        Need::Streamed(limit) => {
+            body.set_limit(body::min(limit, body.limit()));
+            handler.handle(header, Ok(Content::Streamed(body.into_input_stream())))
+        }
+        Need::HeaderOnly => body.close_async(priority, cancellable, move |result| {
+            handler.handle(
+                header,
+                result.map(|()| Content::HeaderOnly).map_err(Error::Body),
+            )
+        }),
+    }
+}
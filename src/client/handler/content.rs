@@ -0,0 +1,13 @@
+use gio::InputStream;
+use glib::Bytes;
+
+/// Body content, provided to `Handler` according to `Need` declared
+pub enum Content {
+    /// All body bytes, for `Need::Buffered`
+    Buffered(Bytes),
+    /// Bounded body stream, for `Need::Streamed`
+    /// * the stream owns the `Connection`
+    Streamed(InputStream),
+    /// No content, for `Need::HeaderOnly`
+    HeaderOnly,
+}
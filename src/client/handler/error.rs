@@ -0,0 +1,25 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug)]
+pub enum Error {
+    Body(crate::client::connection::response::success::body::Error),
+    /// No handler registered for MIME type
+    Handler(String),
+    Mime(crate::client::connection::response::success::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Body(e) => {
+                write!(f, "Body error: {e}")
+            }
+            Self::Handler(mime) => {
+                write!(f, "Handler not found for `{mime}`")
+            }
+            Self::Mime(e) => {
+                write!(f, "MIME error: {e}")
+            }
+        }
+    }
+}
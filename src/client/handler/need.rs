@@ -0,0 +1,12 @@
+/// Body content type, expected by `Handler`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Need {
+    /// Read all body bytes into memory
+    /// * max bytes total, `None` for unlimited (`SizeLimits` policy is applied too, if stricter)
+    Buffered(Option<usize>),
+    /// Get body as [InputStream](https://docs.gtk.org/gio/class.InputStream.html)
+    /// * max bytes total, `None` for unlimited (`SizeLimits` policy is applied too, if stricter)
+    Streamed(Option<usize>),
+    /// Header only, the connection is closing before `Handler` call
+    HeaderOnly,
+}
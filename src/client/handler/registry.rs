@@ -0,0 +1,111 @@
+use super::{Error, Handler};
+use crate::client::{connection::response::Success, mime_pattern};
+use std::rc::Rc;
+
+/// `Handler` registry by MIME type patterns
+/// * resolve the most specific `Handler` registered for `Success` response
+#[derive(Default, Clone)]
+pub struct Registry {
+    handlers: Vec<(String, Rc<dyn Handler>)>,
+}
+
+impl Registry {
+    // Constructors
+
+    /// Create new empty `Self`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Actions
+
+    /// Register `handler` for MIME `pattern`: exact (`image/png`), type (`image/*`) or any (`*/*`)
+    /// * replace existing handler for the same pattern
+    pub fn register(&mut self, pattern: &str, handler: impl Handler + 'static) {
+        let pattern = pattern.trim().to_lowercase();
+        let handler: Rc<dyn Handler> = Rc::new(handler);
+        match self.handlers.iter_mut().find(|(p, _)| *p == pattern) {
+            Some(h) => h.1 = handler,
+            None => self.handlers.push((pattern, handler)),
+        }
+    }
+
+    /// Unregister handler for MIME `pattern`
+    /// * return `false` if the `pattern` not found
+    pub fn unregister(&mut self, pattern: &str) -> bool {
+        let pattern = pattern.trim().to_lowercase();
+        let len = self.handlers.len();
+        self.handlers.retain(|(p, _)| *p != pattern);
+        len != self.handlers.len()
+    }
+
+    // Getters
+
+    /// Get the most specific `Handler` for the MIME type of `success` response
+    /// * return `Err` if the MIME type could not be parsed, or no handler found
+    /// * the `success` is not consumed, so the caller could process it another way on `Err`
+    /// * register `*/*` pattern handler (e.g. "save to disk") to catch any other MIME type
+    /// * see `super::dispatch` (or `Client::dispatch`) to pass the response to the `Handler` found
+    pub fn resolve(&self, success: &Success) -> Result<Rc<dyn Handler>, Error> {
+        let mime = success.mime().map_err(Error::Mime)?;
+        self.lookup(&mime).ok_or(Error::Handler(mime))
+    }
+
+    /// Get the most specific `Handler` for `mime` type
+    pub fn lookup(&self, mime: &str) -> Option<Rc<dyn Handler>> {
+        self.handlers
+            .iter()
+            .filter_map(|(pattern, handler)| {
+                mime_pattern::matches(pattern, mime).map(|specificity| (specificity, handler))
+            })
+            .max_by_key(|(specificity, _)| *specificity)
+            .map(|(_, handler)| handler.clone())
+    }
+}
+
+#[test]
+fn test() {
+    use super::{Content, Need};
+    use crate::client::connection::response::success::default::Header;
+
+    struct H(Need);
+    impl Handler for H {
+        fn need(&self) -> Need {
+            self.0
+        }
+        fn handle(&self, _: Header, _: Result<Content, Error>) {}
+    }
+
+    let mut r = Registry::new();
+    assert!(r.lookup("text/gemini").is_none());
+
+    r.register("*/*", H(Need::HeaderOnly));
+    r.register("image/*", H(Need::Streamed(None)));
+    r.register("text/gemini", H(Need::Buffered(Some(1))));
+
+    assert_eq!(
+        r.lookup("text/gemini").unwrap().need(),
+        Need::Buffered(Some(1))
+    );
+    assert_eq!(r.lookup("image/png").unwrap().need(), Need::Streamed(None));
+    assert_eq!(r.lookup("audio/ogg").unwrap().need(), Need::HeaderOnly);
+
+    r.register("text/gemini", H(Need::Buffered(Some(2))));
+    assert_eq!(
+        r.lookup("text/gemini").unwrap().need(),
+        Need::Buffered(Some(2))
+    );
+
+    assert!(r.unregister("*/*"));
+    assert!(!r.unregister("*/*"));
+    assert!(r.lookup("audio/ogg").is_none());
+
+    // the response is not consumed on no match
+    let s = Success::from_utf8(b"20 audio/ogg\r\n").unwrap();
+    assert!(matches!(r.resolve(&s), Err(Error::Handler(ref mime)) if mime == "audio/ogg"));
+    assert_eq!(s.as_header_str(), "20 audio/ogg\r\n");
+    let s = Success::from_utf8(b"20 audio\r\n").unwrap();
+    assert!(matches!(r.resolve(&s), Err(Error::Mime(_))));
+    let s = Success::from_utf8(b"20 text/gemini; lang=en\r\n").unwrap();
+    assert_eq!(r.resolve(&s).unwrap().need(), Need::Buffered(Some(2)));
+}
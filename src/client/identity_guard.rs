@@ -0,0 +1,374 @@
+pub mod action;
+pub mod scope;
+pub mod violation;
+
+pub use action::Action;
+pub use scope::Scope;
+pub use violation::Violation;
+
+use crate::gio::tls_certificate;
+use gio::TlsCertificate;
+use glib::{ChecksumType, Uri};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{Debug, Formatter, Result as FmtResult},
+    rc::Rc,
+};
+
+/// Violation event callback, e.g. to notify the user
+pub type OnViolation = Rc<dyn Fn(&Violation)>;
+
+/// Client certificate leak protection, across redirects and hosts
+/// * binds every client certificate (identity) to its scopes: the host and port,
+///   or the host, port and path prefix
+/// * the request out of the scopes is refused, or sent without the certificate, by `Action`
+/// * the certificate not bound is sent to any request, so the protection is opt-in:
+///   bind the certificates (e.g. restored by `IdentityStore`), or enable the auto-bind
+/// * clones share the same bindings
+///
+/// ``` rust
+/// use ggemini::client::identity_guard::{Action, IdentityGuard};
+///
+/// let mut guard = IdentityGuard::new(Action::Drop);
+/// guard.set_auto_bind(true);
+///
+/// assert_eq!(guard.action(), Action::Drop);
+/// assert!(guard.is_auto_bind());
+/// ```
+#[derive(Clone)]
+pub struct IdentityGuard {
+    action: Action,
+    /// Fingerprint, scopes
+    bindings: Rc<RefCell<HashMap<String, Vec<Scope>>>>,
+    is_auto_bind: bool,
+    on_violation: Option<OnViolation>,
+}
+
+impl Default for IdentityGuard {
+    fn default() -> Self {
+        Self::new(Action::default())
+    }
+}
+
+impl IdentityGuard {
+    // Constructors
+
+    /// Create new `Self` with `action` for the requests out of scope
+    pub fn new(action: Action) -> Self {
+        Self {
+            action,
+            bindings: Rc::new(RefCell::new(HashMap::new())),
+            is_auto_bind: false,
+            on_violation: None,
+        }
+    }
+
+    // Actions
+
+    /// Check the request to `uri` with the client `certificate`
+    /// * return the certificate to send with the request, `None` if dropped
+    /// * return `Violation` if the request is refused
+    /// * emit the violation event in both cases
+    /// * the certificate not bound (or without fingerprint available) is sent as is
+    pub fn check(
+        &self,
+        uri: &Uri,
+        certificate: Option<TlsCertificate>,
+    ) -> Result<Option<TlsCertificate>, Violation> {
+        let certificate = match certificate {
+            Some(certificate) => certificate,
+            None => return Ok(None),
+        };
+        match identity(&certificate) {
+            Some(identity) => match self.check_identity(uri, &identity) {
+                Ok(true) => Ok(Some(certificate)),
+                Ok(false) => Ok(None),
+                Err(violation) => Err(violation),
+            },
+            None => Ok(Some(certificate)),
+        }
+    }
+
+    /// Check the request to `uri` with the client certificate by its SHA-256 `identity`
+    /// * return `true` to send the certificate with the request, `false` if dropped
+    /// * see `check` for details
+    pub fn check_identity(&self, uri: &Uri, identity: &str) -> Result<bool, Violation> {
+        let identity = identity.to_lowercase();
+        let scopes = match self.bindings.borrow().get(&identity) {
+            Some(scopes) if !scopes.is_empty() => scopes.clone(),
+            _ => return Ok(true),
+        };
+        if scopes.iter().any(|scope| scope.contains(uri)) {
+            return Ok(true);
+        }
+        let violation = Violation {
+            uri: uri.to_str().to_string(),
+            identity,
+            scopes,
+            action: self.action,
+        };
+        if let Some(ref callback) = self.on_violation {
+            callback(&violation)
+        }
+        match self.action {
+            Action::Refuse => Err(violation),
+            Action::Drop => Ok(false),
+        }
+    }
+
+    /// Bind the client `certificate` not bound to the host and port of `uri`,
+    /// if the auto-bind is enabled
+    /// * call when the request with the certificate succeeded (e.g. `Client` does it,
+    ///   if the response is not the certificate failure)
+    pub fn confirm(&self, uri: &Uri, certificate: &TlsCertificate) {
+        if let Some(identity) = identity(certificate) {
+            self.confirm_identity(uri, &identity)
+        }
+    }
+
+    /// Bind the client certificate not bound by its SHA-256 `identity`
+    /// to the host and port of `uri`, if the auto-bind is enabled
+    /// * see `confirm` for details
+    pub fn confirm_identity(&self, uri: &Uri, identity: &str) {
+        if !self.is_auto_bind {
+            return;
+        }
+        let scope = match Scope::from_uri(uri) {
+            Some(scope) => scope,
+            None => return,
+        };
+        let mut bindings = self.bindings.borrow_mut();
+        let scopes = bindings.entry(identity.to_lowercase()).or_default();
+        if scopes.is_empty() {
+            scopes.push(scope)
+        }
+    }
+
+    /// Bind the client `certificate` to `scope`, in addition to existing ones
+    pub fn bind(
+        &self,
+        certificate: &TlsCertificate,
+        scope: Scope,
+    ) -> Result<(), tls_certificate::Error> {
+        self.bind_identity(
+            &tls_certificate::fingerprint(certificate, ChecksumType::Sha256)?,
+            scope,
+        );
+        Ok(())
+    }
+
+    /// Bind the client certificate by its SHA-256 `identity` (fingerprint) to `scope`,
+    /// in addition to existing ones
+    /// * e.g. to restore the scopes saved by `IdentityStore`
+    pub fn bind_identity(&self, identity: &str, scope: Scope) {
+        let mut bindings = self.bindings.borrow_mut();
+        let scopes = bindings.entry(identity.to_lowercase()).or_default();
+        if !scopes.contains(&scope) {
+            scopes.push(scope)
+        }
+    }
+
+    /// Remove all scopes bound to the client `certificate`
+    pub fn unbind(&self, certificate: &TlsCertificate) -> Result<(), tls_certificate::Error> {
+        let identity = tls_certificate::fingerprint(certificate, ChecksumType::Sha256)?;
+        self.bindings.borrow_mut().remove(&identity);
+        Ok(())
+    }
+
+    /// Run `callback` on every request out of scope, e.g. to notify the user
+    pub fn connect_violation(&mut self, callback: impl Fn(&Violation) + 'static) {
+        self.on_violation = Some(Rc::new(callback))
+    }
+
+    // Getters
+
+    /// Get scopes bound to the client `certificate`
+    pub fn scopes(&self, certificate: &TlsCertificate) -> Vec<Scope> {
+        identity(certificate)
+            .map(|identity| self.identity_scopes(&identity))
+            .unwrap_or_default()
+    }
+
+    /// Get scopes bound to the client certificate by its SHA-256 `identity`
+    pub fn identity_scopes(&self, identity: &str) -> Vec<Scope> {
+        self.bindings
+            .borrow()
+            .get(&identity.to_lowercase())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Get action for the requests out of scope
+    pub fn action(&self) -> Action {
+        self.action
+    }
+
+    /// Check the client certificate not bound is bound to the host and port
+    /// of its first successful request
+    pub fn is_auto_bind(&self) -> bool {
+        self.is_auto_bind
+    }
+
+    // Setters
+
+    /// Change action for the requests out of scope
+    pub fn set_action(&mut self, action: Action) {
+        self.action = action
+    }
+
+    /// Bind the client certificate not bound to the host and port of its first successful request
+    /// (`true`), or allow it for any request (`false` by default)
+    pub fn set_auto_bind(&mut self, is_enabled: bool) {
+        self.is_auto_bind = is_enabled
+    }
+}
+
+impl Debug for IdentityGuard {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("IdentityGuard")
+            .field("action", &self.action)
+            .field("bindings", &self.bindings)
+            .field("is_auto_bind", &self.is_auto_bind)
+            .field("on_violation", &self.on_violation.is_some())
+            .finish()
+    }
+}
+
+// Tools
+
+/// Get SHA-256 fingerprint of the client `certificate`, if available
+fn identity(certificate: &TlsCertificate) -> Option<String> {
+    tls_certificate::fingerprint(certificate, ChecksumType::Sha256).ok()
+}
+
+#[test]
+fn test() {
+    use glib::UriFlags;
+    use std::cell::Cell;
+
+    const CERT: &str = r#"-----BEGIN CERTIFICATE-----
+MIIBfTCCASOgAwIBAgIUK+m77CjLxtgE+r2e3MJvGflcm2IwCgYIKoZIzj0EAwIw
+EzERMA8GA1UEAwwIaWRlbnRpdHkwIBcNMjYxMDE4MjAwOTI5WhgPMjEyNjA5MjQy
+MDA5MjlaMBMxETAPBgNVBAMMCGlkZW50aXR5MFkwEwYHKoZIzj0CAQYIKoZIzj0D
+AQcDQgAE3KM3tvQEodU3O/4JAiy7IvD4WIYLDSJiMlbQPdGRjPRInC2a9HbEBdPI
+4XGGv1YEjad21fv7W7BoX29FBmoia6NTMFEwHQYDVR0OBBYEFGhQnSFJOUdWL7pD
+PESBFDKdDNEHMB8GA1UdIwQYMBaAFGhQnSFJOUdWL7pDPESBFDKdDNEHMA8GA1Ud
+EwEB/wQFMAMBAf8wCgYIKoZIzj0EAwIDSAAwRQIhAODsjQrgOkrzVDDRaB0Hx5ys
+tS83F5aUj//C6CZzlJykAiAKTuN+LHemtt1AbDRrt7QP14GveBCnyNXX/D8Z/5ZZ
+HQ==
+-----END CERTIFICATE-----"#;
+    const RSA_CERT: &str = r#"-----BEGIN CERTIFICATE-----
+MIIBdTCCAR+gAwIBAgIUHjv++KkTn6nlQH9e7XSWvflRAfswDQYJKoZIhvcNAQEL
+BQAwDjEMMAoGA1UEAwwDcnNhMCAXDTI2MTAxODIwMDkyOVoYDzIxMjYwOTI0MjAw
+OTI5WjAOMQwwCgYDVQQDDANyc2EwXDANBgkqhkiG9w0BAQEFAANLADBIAkEAzj0W
+mdqBW9eZXtSb36odJt7b+KMIb2rgJSG55fv2184HAvqcGmy6/nzEf3XGY0XJ3F91
+8xbi0RNTqC5tWbl5wwIDAQABo1MwUTAdBgNVHQ4EFgQUu3OQk9qP9dbvhTApkDXz
+F/xZg6AwHwYDVR0jBBgwFoAUu3OQk9qP9dbvhTApkDXzF/xZg6AwDwYDVR0TAQH/
+BAUwAwEB/zANBgkqhkiG9w0BAQsFAANBAGmCCBwhLNzyVvfkF7T7wVTcJc7EuU16
+CPxzEhBclXNkzZs/XVR0w5hvlHNUIHMmOLT+SbWx+RPnji7axLklAPw=
+-----END CERTIFICATE-----"#;
+
+    let identity = |pem| {
+        glib::compute_checksum_for_data(
+            ChecksumType::Sha256,
+            &crate::gio::identity::pem::blocks(pem)[0].data,
+        )
+        .unwrap()
+        .to_string()
+    };
+    let uri = |s| Uri::parse(s, UriFlags::NONE).unwrap();
+
+    let a = identity(CERT);
+    let b = identity(RSA_CERT);
+
+    // not bound: allowed for any request, not bound on success by default
+    let mut guard = IdentityGuard::new(Action::Refuse);
+    assert!(guard.check_identity(&uri("gemini://a.org/"), &a).unwrap());
+    guard.confirm_identity(&uri("gemini://a.org/"), &a);
+    assert!(guard.identity_scopes(&a).is_empty());
+
+    // auto-bind on success
+    guard.set_auto_bind(true);
+    assert!(guard.check_identity(&uri("gemini://a.org/"), &a).unwrap());
+    guard.confirm_identity(&uri("gemini://a.org/"), &a);
+    assert_eq!(guard.identity_scopes(&a), vec![Scope::host("a.org")]);
+
+    // the next success does not bind the other host
+    guard.confirm_identity(&uri("gemini://b.org/"), &a);
+    assert_eq!(guard.identity_scopes(&a), vec![Scope::host("a.org")]);
+
+    // in scope
+    assert!(
+        guard
+            .check_identity(&uri("gemini://A.org/page"), &a)
+            .unwrap()
+    );
+
+    // out of scope: refuse, with the callback fired
+    let violations = Rc::new(Cell::new(0));
+    guard.connect_violation({
+        let violations = violations.clone();
+        move |_| violations.set(violations.get() + 1)
+    });
+    let violation = guard
+        .check_identity(&uri("gemini://b.org/"), &a.to_uppercase())
+        .unwrap_err();
+    assert_eq!(violation.uri, "gemini://b.org/");
+    assert_eq!(violation.identity, a);
+    assert_eq!(violation.scopes, vec![Scope::host("a.org")]);
+    assert_eq!(violation.action, Action::Refuse);
+    assert_eq!(violations.get(), 1);
+
+    // out of scope: drop, including the other port of the same host
+    guard.set_action(Action::Drop);
+    assert!(!guard.check_identity(&uri("gemini://b.org/"), &a).unwrap());
+    assert!(
+        !guard
+            .check_identity(&uri("gemini://a.org:1966/"), &a)
+            .unwrap()
+    );
+    assert!(
+        guard
+            .check_identity(&uri("gemini://a.org:1965/"), &a)
+            .unwrap()
+    );
+    assert_eq!(violations.get(), 3);
+
+    // the path scope, shared by clones
+    let clone = guard.clone();
+    clone.bind_identity(&b, Scope::path("b.org", "/app"));
+    assert!(
+        guard
+            .check_identity(&uri("gemini://b.org/app/page"), &b)
+            .unwrap()
+    );
+    assert!(
+        !guard
+            .check_identity(&uri("gemini://b.org/application"), &b)
+            .unwrap()
+    );
+    assert!(
+        !guard
+            .check_identity(&uri("gemini://a.org/app"), &b)
+            .unwrap()
+    );
+    assert_eq!(violations.get(), 5);
+
+    // auto-bind the host and port
+    guard.confirm_identity(&uri("gemini://c.org:1966/"), &b);
+    assert!(
+        guard
+            .check_identity(&uri("gemini://b.org/app"), &b)
+            .unwrap()
+    );
+    let c = "c".repeat(64);
+    guard.confirm_identity(&uri("gemini://c.org:1966/"), &c);
+    assert!(
+        guard
+            .check_identity(&uri("gemini://c.org:1966/page"), &c)
+            .unwrap()
+    );
+    assert!(!guard.check_identity(&uri("gemini://c.org/"), &c).unwrap());
+    assert_eq!(violations.get(), 6);
+}
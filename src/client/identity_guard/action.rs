@@ -0,0 +1,9 @@
+/// Action for the request out of the client certificate `Scope`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Action {
+    /// Return `Err`, do not send the request
+    #[default]
+    Refuse,
+    /// Send the request without the client certificate (as guest)
+    Drop,
+}
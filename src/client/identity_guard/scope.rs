@@ -0,0 +1,171 @@
+use crate::DEFAULT_PORT;
+use glib::Uri;
+use std::fmt::{Display, Formatter, Result};
+
+/// Client certificate scope: the host and port, or the host, port and path prefix
+/// * the host is case insensitive
+/// * the port is `crate::DEFAULT_PORT` by default, as for the `Uri` without port
+/// * the path prefix matches whole segments, e.g. `/app` matches `/app` and `/app/page`,
+///   but not `/application`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scope {
+    pub host: String,
+    pub port: u16,
+    pub path: Option<String>,
+}
+
+impl Scope {
+    // Constructors
+
+    /// Create new `Self` for all paths of the `host` on default port
+    pub fn host(host: &str) -> Self {
+        Self {
+            host: host.to_lowercase(),
+            port: DEFAULT_PORT,
+            path: None,
+        }
+    }
+
+    /// Create new `Self` for the `path` prefix of the `host` on default port
+    pub fn path(host: &str, path: &str) -> Self {
+        Self {
+            host: host.to_lowercase(),
+            port: DEFAULT_PORT,
+            path: Some(path.to_string()),
+        }
+    }
+
+    /// Create new `Self` for all paths of the `uri` host and port
+    /// * return `None` if the `uri` has no host
+    pub fn from_uri(uri: &Uri) -> Option<Self> {
+        Some(Self {
+            host: uri.host()?.to_lowercase(),
+            port: port(uri),
+            path: None,
+        })
+    }
+
+    /// Parse `Self` from the `host`, `host:port`, `host/path` or `host:port/path` string
+    /// (see `Display`)
+    /// * the IPv6 host is enclosed in brackets, e.g. `[::1]:1965`
+    pub fn parse(value: &str) -> Self {
+        let (authority, path) = match value.find('/') {
+            Some(i) => (&value[..i], Some(&value[i..])),
+            None => (value, None),
+        };
+        let (host, port) = match authority.strip_prefix('[') {
+            Some(ipv6) => match ipv6.split_once(']') {
+                Some((host, port)) => (host, port.strip_prefix(':')),
+                None => (ipv6, None),
+            },
+            None => match authority.rsplit_once(':') {
+                Some((host, port)) if !host.contains(':') => (host, Some(port)),
+                _ => (authority, None),
+            },
+        };
+        Self {
+            host: host.to_lowercase(),
+            port: port.and_then(|p| p.parse().ok()).unwrap_or(DEFAULT_PORT),
+            path: path.map(|p| p.to_string()),
+        }
+    }
+
+    // Getters
+
+    /// Check `uri` is in `Self` scope
+    pub fn contains(&self, uri: &Uri) -> bool {
+        if !uri
+            .host()
+            .is_some_and(|host| host.eq_ignore_ascii_case(&self.host))
+            || port(uri) != self.port
+        {
+            return false;
+        }
+        let prefix = match self.path {
+            Some(ref prefix) => prefix,
+            None => return true,
+        };
+        let path = match uri.path().as_str() {
+            "" => "/".to_string(),
+            path => path.to_string(),
+        };
+        if prefix.ends_with('/') {
+            path.starts_with(prefix.as_str())
+        } else {
+            path == *prefix || path.starts_with(&format!("{prefix}/"))
+        }
+    }
+}
+
+impl Display for Scope {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        if self.host.contains(':') {
+            write!(f, "[{}]", self.host)?
+        } else {
+            write!(f, "{}", self.host)?
+        }
+        if self.port != DEFAULT_PORT {
+            write!(f, ":{}", self.port)?
+        }
+        match self.path {
+            Some(ref path) => write!(f, "{path}"),
+            None => Ok(()),
+        }
+    }
+}
+
+// Tools
+
+/// Get port of the `uri`, `crate::DEFAULT_PORT` if not defined
+fn port(uri: &Uri) -> u16 {
+    u16::try_from(uri.port()).unwrap_or(DEFAULT_PORT)
+}
+
+#[test]
+fn test() {
+    use glib::UriFlags;
+
+    let uri = |s| Uri::parse(s, UriFlags::NONE).unwrap();
+
+    let scope = Scope::host("Geminiprotocol.net");
+    assert!(scope.contains(&uri("gemini://geminiprotocol.net")));
+    assert!(scope.contains(&uri("gemini://GEMINIPROTOCOL.NET/docs/")));
+    assert!(!scope.contains(&uri("gemini://example.org/")));
+
+    let scope = Scope::path("geminiprotocol.net", "/app");
+    assert!(scope.contains(&uri("gemini://geminiprotocol.net/app")));
+    assert!(scope.contains(&uri("gemini://geminiprotocol.net/app/page?q")));
+    assert!(!scope.contains(&uri("gemini://geminiprotocol.net/application")));
+    assert!(!scope.contains(&uri("gemini://geminiprotocol.net/")));
+    assert!(!scope.contains(&uri("gemini://example.org/app")));
+
+    let scope = Scope::path("geminiprotocol.net", "/");
+    assert!(scope.contains(&uri("gemini://geminiprotocol.net")));
+
+    // port
+    let scope = Scope::host("geminiprotocol.net");
+    assert!(scope.contains(&uri("gemini://geminiprotocol.net:1965/")));
+    assert!(!scope.contains(&uri("gemini://geminiprotocol.net:1966/")));
+
+    let scope = Scope::from_uri(&uri("gemini://Geminiprotocol.net:1966/page")).unwrap();
+    assert_eq!(scope.port, 1966);
+    assert!(scope.contains(&uri("gemini://geminiprotocol.net:1966/")));
+    assert!(!scope.contains(&uri("gemini://geminiprotocol.net/")));
+
+    let scope = Scope::from_uri(&uri("gemini://[::1]:1966/")).unwrap();
+    assert_eq!(scope.to_string(), "[::1]:1966");
+    assert!(scope.contains(&uri("gemini://[::1]:1966/")));
+
+    for scope in [
+        Scope::host("geminiprotocol.net"),
+        Scope::path("geminiprotocol.net", "/app/"),
+        Scope {
+            port: 1966,
+            ..Scope::path("geminiprotocol.net", "/app")
+        },
+        Scope::from_uri(&uri("gemini://[::1]/")).unwrap(),
+        Scope::from_uri(&uri("gemini://[::1]:1966/")).unwrap(),
+    ] {
+        assert_eq!(Scope::parse(&scope.to_string()), scope);
+    }
+}
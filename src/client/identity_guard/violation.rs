@@ -0,0 +1,32 @@
+use super::{Action, Scope};
+use std::fmt::{Display, Formatter, Result};
+
+/// Request out of the client certificate `Scope`, reported to the UI
+#[derive(Debug, Clone)]
+pub struct Violation {
+    /// Requested URI string
+    pub uri: String,
+    /// SHA-256 fingerprint of the client certificate
+    pub identity: String,
+    /// Scopes bound to the client certificate
+    pub scopes: Vec<Scope>,
+    /// Action applied to the request
+    pub action: Action,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self.action {
+            Action::Refuse => write!(
+                f,
+                "Request to `{}` refused: out of the client certificate scope",
+                self.uri
+            ),
+            Action::Drop => write!(
+                f,
+                "Client certificate dropped for `{}`: out of its scope",
+                self.uri
+            ),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug)]
+pub enum Error {
+    Certificate(crate::gio::tls_certificate::Error),
+    DateTime(glib::BoolError),
+    Directory(std::io::Error),
+    Exists(String),
+    File(glib::Error),
+    Identity(crate::gio::identity::Error),
+    KeyFile(glib::Error),
+    NotFound(String),
+    PrivateKey,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Certificate(e) => {
+                write!(f, "Certificate error: {e}")
+            }
+            Self::DateTime(e) => {
+                write!(f, "Date time error: {e}")
+            }
+            Self::Directory(e) => {
+                write!(f, "Directory error: {e}")
+            }
+            Self::Exists(id) => {
+                write!(f, "Identity `{id}` already exists")
+            }
+            Self::File(e) => {
+                write!(f, "File error: {e}")
+            }
+            Self::Identity(e) => {
+                write!(f, "Identity error: {e}")
+            }
+            Self::KeyFile(e) => {
+                write!(f, "Metadata error: {e}")
+            }
+            Self::NotFound(id) => {
+                write!(f, "Identity `{id}` not found")
+            }
+            Self::PrivateKey => {
+                write!(f, "Private key not found")
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use super::Error;
+use crate::client::identity_guard::Scope;
+use glib::{DateTime, KeyFile, KeyFileFlags};
+use std::path::Path;
+
+const GROUP: &str = "identity";
+
+/// Identity metadata, saved next to the certificate file
+#[derive(Debug, Clone)]
+pub struct Meta {
+    /// SHA-256 fingerprint of the certificate
+    pub id: String,
+    /// User-given name
+    pub name: String,
+    pub created: DateTime,
+    /// Certificate `not_valid_after` date, if any
+    pub expires: Option<DateTime>,
+    /// Scopes to restore by `IdentityStore::bind`
+    pub scopes: Vec<Scope>,
+    pub last_used: Option<DateTime>,
+}
+
+impl Meta {
+    // Constructors
+
+    /// Load `Self` for `id` from the key `file`
+    pub fn load(id: &str, file: &Path) -> Result<Self, Error> {
+        let key_file = KeyFile::new();
+        key_file
+            .load_from_file(file, KeyFileFlags::NONE)
+            .map_err(Error::KeyFile)?;
+        let time = |key| match key_file.int64(GROUP, key) {
+            Ok(t) => DateTime::from_unix_utc(t)
+                .map(Some)
+                .map_err(Error::DateTime),
+            Err(_) => Ok(None),
+        };
+        Ok(Self {
+            id: id.to_string(),
+            name: key_file
+                .string(GROUP, "name")
+                .map_err(Error::KeyFile)?
+                .into(),
+            created: DateTime::from_unix_utc(
+                key_file.int64(GROUP, "created").map_err(Error::KeyFile)?,
+            )
+            .map_err(Error::DateTime)?,
+            expires: time("expires")?,
+            scopes: match key_file.string_list(GROUP, "scopes") {
+                Ok(scopes) => scopes.iter().map(|s| Scope::parse(s.as_str())).collect(),
+                Err(_) => Vec::new(),
+            },
+            last_used: time("last_used")?,
+        })
+    }
+
+    // Actions
+
+    /// Save `Self` to the key `file`, replace existing one
+    pub fn save(&self, file: &Path) -> Result<(), Error> {
+        let key_file = KeyFile::new();
+        key_file.set_string(GROUP, "name", &self.name);
+        key_file.set_int64(GROUP, "created", self.created.to_unix());
+        if let Some(ref expires) = self.expires {
+            key_file.set_int64(GROUP, "expires", expires.to_unix());
+        }
+        if !self.scopes.is_empty() {
+            key_file.set_value(
+                GROUP,
+                "scopes",
+                &self
+                    .scopes
+                    .iter()
+                    .map(|scope| format!("{};", escape(&scope.to_string())))
+                    .collect::<String>(),
+            );
+        }
+        if let Some(ref last_used) = self.last_used {
+            key_file.set_int64(GROUP, "last_used", last_used.to_unix());
+        }
+        key_file.save_to_file(file).map_err(Error::KeyFile)
+    }
+
+    // Getters
+
+    /// Check the certificate is expired by this moment
+    pub fn is_expired(&self) -> bool {
+        self.expires
+            .as_ref()
+            .is_some_and(|expires| expires.to_unix() < glib::real_time() / 1_000_000)
+    }
+}
+
+// Tools
+
+/// Escape the key file list item `value`
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace('\n', "\\n")
+}
+
+#[test]
+fn test() {
+    let file = glib::tmp_dir().join(format!("ggemini-meta-{:08x}.ini", glib::random_int()));
+    let meta = Meta {
+        id: "ab".into(),
+        name: "Name; with separator".into(),
+        created: DateTime::from_unix_utc(1).unwrap(),
+        expires: Some(DateTime::from_unix_utc(2).unwrap()),
+        scopes: vec![
+            Scope::host("geminiprotocol.net"),
+            Scope::path("example.org", "/a;b/"),
+        ],
+        last_used: None,
+    };
+    meta.save(&file).unwrap();
+    let loaded = Meta::load("ab", &file).unwrap();
+    std::fs::remove_file(&file).unwrap();
+
+    assert_eq!(loaded.name, meta.name);
+    assert_eq!(loaded.created, meta.created);
+    assert_eq!(loaded.expires, meta.expires);
+    assert_eq!(loaded.scopes, meta.scopes);
+    assert!(loaded.last_used.is_none());
+    assert!(loaded.is_expired());
+}
@@ -0,0 +1,196 @@
+mod imp;
+
+use super::{Pinning, Validation, pinning::Mismatch, validation::Verification};
+use crate::gio::tls_certificate;
+use gio::{
+    NetworkAddress, TlsCertificate, TlsClientConnection, TlsDatabase,
+    prelude::TlsClientConnectionExt,
+};
+use glib::{
+    Bytes, Object,
+    object::{Cast, IsA},
+    subclass::prelude::ObjectSubclassIsExt,
+};
+
+glib::wrapper! {
+    /// Known hosts trust store, implemented as [TlsDatabase](https://docs.gtk.org/gio/class.TlsDatabase.html)
+    ///
+    /// **Useful for**
+    /// * verify the server certificates by the normal GIO path (`TlsConnection::set_database`)
+    /// * share one trust store between every `Client` and `Connection` in the application
+    ///
+    /// The server certificate is verified by `Validation` mode with `Pinning` policy,
+    /// the unknown host is pinned on the first visit (TOFU)
+    ///
+    /// The verification runs in the TLS thread, so the re-trust decision callback of `Pinning`
+    /// is not in use: the mismatched certificate fails the handshake (`connection::Error::Mismatch`),
+    /// then the application may ask the user in the main loop, call `retrust`
+    /// and repeat the request
+    pub struct KnownHosts(ObjectSubclass<imp::KnownHosts>) @extends TlsDatabase;
+}
+
+impl Default for KnownHosts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KnownHosts {
+    // Constructors
+
+    /// Create new empty `Self`
+    pub fn new() -> Self {
+        Object::new()
+    }
+
+    // Actions
+
+    /// Pin `certificate` for `host`, in addition to existing ones
+    /// * return `Err` if the certificate DER data is not available
+    pub fn add(
+        &self,
+        host: &str,
+        certificate: &TlsCertificate,
+    ) -> Result<(), tls_certificate::Error> {
+        self.imp()
+            .add(host, Bytes::from_owned(tls_certificate::der(certificate)?));
+        Ok(())
+    }
+
+    /// Pin `certificate` for `host`, replacing existing ones (e.g. accepted by the user
+    /// after the handshake failure), then run `connect_changed` callback
+    /// * return `Err` if the certificate DER data is not available
+    pub fn retrust(
+        &self,
+        host: &str,
+        certificate: &TlsCertificate,
+    ) -> Result<(), tls_certificate::Error> {
+        self.imp()
+            .retrust(host, Bytes::from_owned(tls_certificate::der(certificate)?));
+        Ok(())
+    }
+
+    /// Remove all certificates pinned for `host`
+    pub fn remove(&self, host: &str) {
+        self.imp().remove(host)
+    }
+
+    // Getters
+
+    /// Check server `certificate` by the ones pinned for `host`, with the pinning mode
+    /// * return `None` for unknown host (or the certificate DER data is not available)
+    pub fn check(&self, host: &str, certificate: &TlsCertificate) -> Option<Result<(), Mismatch>> {
+        self.imp()
+            .check(host, &tls_certificate::der(certificate).ok()?)
+    }
+
+    /// Get certificates pinned for `host`
+    pub fn certificates(&self, host: &str) -> Vec<TlsCertificate> {
+        self.imp().certificates(host)
+    }
+
+    /// Get all hosts known
+    pub fn hosts(&self) -> Vec<String> {
+        self.imp().hosts()
+    }
+
+    /// Get `Verification` result of the last handshake of `tls_client_connection`, if any
+    /// * the result is bound to the server identity object of the connection,
+    ///   so other connections to the same host do not share it
+    pub fn verification(
+        &self,
+        tls_client_connection: &impl IsA<TlsClientConnection>,
+    ) -> Option<Verification> {
+        self.imp().verification(
+            tls_client_connection
+                .server_identity()?
+                .downcast_ref::<NetworkAddress>()?,
+        )
+    }
+
+    // Setters
+
+    /// Run `callback` when the certificates pinned for the host are changed
+    /// by the first visit or re-trust, e.g. to save them
+    /// * the `callback` is called from the TLS thread
+    pub fn connect_changed(&self, callback: impl Fn(&str) + Send + Sync + 'static) {
+        self.imp().connect_changed(callback)
+    }
+
+    /// Set TOFU pinning and re-trust policy
+    /// * the decision callback of `pinning` is not in use, see `retrust`
+    pub fn set_pinning(&self, pinning: &Pinning) {
+        self.imp().set_pinning(pinning)
+    }
+
+    /// Set server certificate validation mode (`Validation::Tofu` by default)
+    pub fn set_validation(&self, validation: Validation) {
+        self.imp().set_validation(validation)
+    }
+}
+
+#[test]
+fn test() {
+    use super::pinning::Mode;
+    use glib::object::ObjectExt;
+    use std::sync::{Arc, Mutex};
+
+    // self-signed `CN=a` (EC P-256), `B` is re-issued with the same key, `C` with new one
+    const A: &str = "MIIBbDCCAROgAwIBAgIUQGZMcyOPzyhA2zvuE1AHJlv5sGUwCgYIKoZIzj0EAwIwDDEKMAgGA1UEAwwBYTAeFw0yNjEwMTgyMDMyMzdaFw0zNjEwMTUyMDMyMzdaMAwxCjAIBgNVBAMMAWEwWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAAR3aOrth2IiIuGfgZV1YYQdRntChwlHRH/l6OgJ/qQs5Q2+HZCfjTa364d7IqiM75iZWrZvcKHl9yNWgkLf5jHio1MwUTAdBgNVHQ4EFgQUjIj5f3rVsnClHd74kSlccQKKzwEwHwYDVR0jBBgwFoAUjIj5f3rVsnClHd74kSlccQKKzwEwDwYDVR0TAQH/BAUwAwEB/zAKBggqhkjOPQQDAgNHADBEAiAGvGILDfaGtKrYZwkVxeK7WslOLswEe4OHzKKj0GQjbwIgYIFNdvfFlKqYAgw1xSrOWzu5l3ztXipl4nHV6pUlFLU=";
+    const B: &str = "MIIBWjCCAQCgAwIBAgIBAjAKBggqhkjOPQQDAjAMMQowCAYDVQQDDAFhMB4XDTI2MTAxODIwMzIzN1oXDTM2MTAxNTIwMzIzN1owDDEKMAgGA1UEAwwBYTBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABHdo6u2HYiIi4Z+BlXVhhB1Ge0KHCUdEf+Xo6An+pCzlDb4dkJ+NNrfrh3siqIzvmJlatm9woeX3I1aCQt/mMeKjUzBRMB0GA1UdDgQWBBSMiPl/etWycKUd3viRKVxxAorPATAfBgNVHSMEGDAWgBSMiPl/etWycKUd3viRKVxxAorPATAPBgNVHRMBAf8EBTADAQH/MAoGCCqGSM49BAMCA0gAMEUCIQCZq58qeTns2yF02HlwmUl3E/lKRDYR+Dgljb7F7TUPdwIgNeAaXwDz1spRpWy2j4GExPV89sMJfjzWyJwCLyGFlfk=";
+    const C: &str = "MIIBbTCCAROgAwIBAgIUEQWOBobgOqFKewbjLS9BfLeWY+gwCgYIKoZIzj0EAwIwDDEKMAgGA1UEAwwBYTAeFw0yNjEwMTgyMDMyMzdaFw0zNjEwMTUyMDMyMzdaMAwxCjAIBgNVBAMMAWEwWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAASfs76HfxEEh5zdDRTn2Fz7EolgtUzMC9BSCB8+j3WMxYl/pu+evGsCEWXseGOitWmto/QQl0VXBFJXA/JlVvugo1MwUTAdBgNVHQ4EFgQUs7DbK6q3L6hYY3zwuuTvsCEdfqEwHwYDVR0jBBgwFoAUs7DbK6q3L6hYY3zwuuTvsCEdfqEwDwYDVR0TAQH/BAUwAwEB/zAKBggqhkjOPQQDAgNIADBFAiEAlGVcRjI3qFf7OSQJwbPkS0ruKujGs+syHx5ijI0Z0TcCIAWddhVFlQ/DbnTcwJwjWj2Wozo9cw2HQrrEkNByL0Fi";
+
+    let known_hosts = KnownHosts::new();
+    assert!(known_hosts.upcast_ref::<TlsDatabase>().is::<KnownHosts>());
+    assert!(known_hosts.hosts().is_empty());
+    assert!(known_hosts.certificates("geminiprotocol.net").is_empty());
+
+    // the results are bound to the server identity of the connection, not to the host
+    let a = NetworkAddress::new("geminiprotocol.net", 1965);
+    let b = NetworkAddress::new("geminiprotocol.net", 1965);
+    let accepted = Verification {
+        ca: None,
+        tofu: None,
+        is_accepted: true,
+    };
+    known_hosts.imp().record(&a, accepted);
+    assert_eq!(known_hosts.imp().verification(&a), Some(accepted));
+    assert!(known_hosts.imp().verification(&b).is_none());
+    drop(a);
+    known_hosts.imp().record(&b, accepted);
+    assert_eq!(known_hosts.imp().verifications.lock().unwrap().len(), 1);
+
+    let der = |base64| Bytes::from_owned(glib::base64_decode(base64));
+    let imp = known_hosts.imp();
+    let changed = Arc::new(Mutex::new(Vec::new()));
+    known_hosts.connect_changed({
+        let changed = changed.clone();
+        move |host| changed.lock().unwrap().push(host.to_string())
+    });
+
+    // pin
+    assert!(imp.check("a", &der(A)).is_none());
+    imp.add("A", der(A));
+    assert_eq!(known_hosts.hosts(), ["a"]);
+    assert_eq!(imp.pinned("a"), [der(A)]);
+
+    // match and mismatch
+    assert_eq!(imp.check("a", &der(A)), Some(Ok(())));
+    assert_eq!(imp.check("a", &der(B)), Some(Err(Mismatch::Certificate)));
+    assert_eq!(imp.check("a", &der(C)), Some(Err(Mismatch::PublicKey)));
+    let mut pinning = Pinning::default();
+    pinning.set("a", Mode::PublicKey);
+    known_hosts.set_pinning(&pinning);
+    assert_eq!(imp.check("a", &der(B)), Some(Ok(())));
+    assert_eq!(imp.check("a", &der(C)), Some(Err(Mismatch::PublicKey)));
+
+    // re-trust
+    assert!(changed.lock().unwrap().is_empty());
+    imp.retrust("a", der(C));
+    assert_eq!(*changed.lock().unwrap(), ["a"]);
+    assert_eq!(imp.check("a", &der(C)), Some(Ok(())));
+    assert_eq!(imp.check("a", &der(A)), Some(Err(Mismatch::PublicKey)));
+
+    known_hosts.remove("A");
+    assert!(known_hosts.hosts().is_empty());
+}
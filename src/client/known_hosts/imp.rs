@@ -0,0 +1,245 @@
+use crate::{
+    client::{
+        Pinning, Validation,
+        pinning::{self, Mismatch, Policy},
+        validation::{self, Verification},
+    },
+    gio::tls_certificate,
+};
+use gio::{
+    NetworkAddress, SocketConnectable, TlsCertificate, TlsCertificateFlags, TlsDatabase,
+    prelude::{NetworkAddressExt, SocketConnectableExt},
+    subclass::prelude::*,
+};
+use glib::{
+    Bytes, WeakRef,
+    object::{Cast, ObjectExt},
+    translate::{Borrowed, IntoGlib, from_glib_borrow},
+};
+use std::{
+    ffi::c_char,
+    sync::{Arc, Mutex},
+};
+
+type OnChanged = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// `KnownHosts` implementation
+/// * `verify_chain` is called by the TLS backend from the handshake thread,
+///   so the state is guarded by `Mutex`, and holds the `Send` types only
+#[derive(Default)]
+pub struct KnownHosts {
+    /// Pinned certificates DER data, by host
+    hosts: Mutex<Vec<(String, Vec<Bytes>)>>,
+    on_changed: Mutex<Option<OnChanged>>,
+    pinning: Mutex<Policy>,
+    validation: Mutex<Validation>,
+    /// Last handshake results, by the server identity of the `TlsClientConnection`
+    /// * the identity is unique for every `Connection`, so the results are not shared
+    pub(super) verifications: Mutex<Vec<(WeakRef<NetworkAddress>, Verification)>>,
+}
+
+impl KnownHosts {
+    /// Pin certificate `der` data for `host`, in addition to existing ones
+    pub fn add(&self, host: &str, der: Bytes) {
+        let host = host.to_lowercase();
+        let mut hosts = self.hosts.lock().unwrap();
+        match hosts.iter_mut().find(|(h, _)| *h == host) {
+            Some((_, pinned)) => pinned.push(der),
+            None => hosts.push((host, vec![der])),
+        }
+    }
+
+    /// Pin certificate `der` data for `host`, replacing existing ones
+    pub fn retrust(&self, host: &str, der: Bytes) {
+        self.remove(host);
+        self.add(host, der);
+        self.changed(host)
+    }
+
+    pub fn remove(&self, host: &str) {
+        let host = host.to_lowercase();
+        self.hosts.lock().unwrap().retain(|(h, _)| *h != host)
+    }
+
+    /// Check certificate `der` data by the ones pinned for `host`, with the pinning mode
+    /// * return `None` for unknown host
+    pub fn check(&self, host: &str, der: &[u8]) -> Option<Result<(), Mismatch>> {
+        let pinned = self.pinned(host);
+        if pinned.is_empty() {
+            return None;
+        }
+        let mode = self.pinning.lock().unwrap().mode(host);
+        Some(pinning::check_der(mode, &pinned, der))
+    }
+
+    pub fn certificates(&self, host: &str) -> Vec<TlsCertificate> {
+        self.pinned(host)
+            .iter()
+            .filter_map(|der| tls_certificate::from_der(der).ok())
+            .collect()
+    }
+
+    /// Get certificates DER data pinned for `host`
+    pub fn pinned(&self, host: &str) -> Vec<Bytes> {
+        let host = host.to_lowercase();
+        self.hosts
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(h, _)| *h == host)
+            .map(|(_, pinned)| pinned.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn hosts(&self) -> Vec<String> {
+        self.hosts
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(h, _)| h.clone())
+            .collect()
+    }
+
+    pub fn verification(&self, identity: &NetworkAddress) -> Option<Verification> {
+        self.verifications
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(i, _)| i.upgrade().as_ref() == Some(identity))
+            .map(|(_, v)| *v)
+    }
+
+    pub fn connect_changed(&self, callback: impl Fn(&str) + Send + Sync + 'static) {
+        self.on_changed.lock().unwrap().replace(Arc::new(callback));
+    }
+
+    pub fn set_pinning(&self, pinning: &Pinning) {
+        *self.pinning.lock().unwrap() = pinning.policy().clone()
+    }
+
+    pub fn set_validation(&self, validation: Validation) {
+        *self.validation.lock().unwrap() = validation
+    }
+
+    /// Record `verification` result for the server `identity`,
+    /// forget the results of the connections already dropped
+    pub fn record(&self, identity: &NetworkAddress, verification: Verification) {
+        let mut verifications = self.verifications.lock().unwrap();
+        verifications.retain(|(i, _)| i.upgrade().is_some_and(|i| i != *identity));
+        verifications.push((identity.downgrade(), verification));
+    }
+
+    /// Run `on_changed` callback for `host`, if connected
+    fn changed(&self, host: &str) {
+        // clone the callback, to not lock it while running
+        let on_changed = self.on_changed.lock().unwrap().clone();
+        if let Some(on_changed) = on_changed {
+            on_changed(host)
+        }
+    }
+
+    /// Verify the server certificate (`chain` leaf) for `identity`
+    fn verify_chain(
+        &self,
+        chain: &TlsCertificate,
+        identity: Option<&SocketConnectable>,
+    ) -> TlsCertificateFlags {
+        let host = identity.map(host).unwrap_or_default();
+        let der = match tls_certificate::der(chain) {
+            Ok(der) => der,
+            Err(_) => return TlsCertificateFlags::GENERIC_ERROR,
+        };
+        let pinned_der = self.pinned(&host);
+        let pinned = self.certificates(&host);
+        // never handle the host as unknown (the first visit), if the pinned data is not valid
+        if pinned.len() != pinned_der.len() {
+            return TlsCertificateFlags::GENERIC_ERROR;
+        }
+
+        // the re-trust decision callback is not in use, see `super::KnownHosts::retrust`
+        let pinning = Pinning::from_policy(self.pinning.lock().unwrap().clone());
+        let validation = *self.validation.lock().unwrap();
+
+        let verification = validation::verify(
+            validation,
+            &pinning,
+            &host,
+            identity,
+            (!pinned.is_empty()).then_some(&pinned[..]),
+            chain,
+        );
+
+        // pin the certificate on the first visit or re-trust
+        let is_changed = verification.is_accepted
+            && (pinned.is_empty()
+                || (verification.tofu.is_some() && !pinned_der.iter().any(|p| **p == der[..])));
+        if is_changed && validation.is_tofu() {
+            self.retrust(&host, Bytes::from_owned(der))
+        }
+
+        if let Some(identity) = identity.and_then(|i| i.downcast_ref::<NetworkAddress>()) {
+            self.record(identity, verification)
+        }
+
+        if verification.is_accepted {
+            TlsCertificateFlags::empty()
+        } else {
+            match verification.ca {
+                Some(flags) if !flags.is_empty() => flags,
+                _ => TlsCertificateFlags::UNKNOWN_CA,
+            }
+        }
+    }
+}
+
+#[glib::object_subclass]
+impl ObjectSubclass for KnownHosts {
+    const NAME: &'static str = "GGeminiKnownHosts";
+    type Type = super::KnownHosts;
+    type ParentType = TlsDatabase;
+}
+
+impl ObjectImpl for KnownHosts {}
+
+// `TlsDatabase` has no subclassing support in gio-rs, so override the `verify_chain`
+// virtual method manually; the default async implementation calls it in the thread
+
+unsafe impl IsSubclassable<KnownHosts> for TlsDatabase {
+    fn class_init(class: &mut glib::Class<Self>) {
+        Self::parent_class_init::<KnownHosts>(class);
+        let klass = class.as_mut();
+        klass.verify_chain = Some(verify_chain);
+    }
+}
+
+unsafe extern "C" fn verify_chain(
+    ptr: *mut gio::ffi::GTlsDatabase,
+    chain: *mut gio::ffi::GTlsCertificate,
+    _purpose: *const c_char,
+    identity: *mut gio::ffi::GSocketConnectable,
+    _interaction: *mut gio::ffi::GTlsInteraction,
+    _flags: gio::ffi::GTlsDatabaseVerifyFlags,
+    _cancellable: *mut gio::ffi::GCancellable,
+    _error: *mut *mut glib::ffi::GError,
+) -> gio::ffi::GTlsCertificateFlags {
+    unsafe {
+        let instance = &*(ptr as *mut <KnownHosts as ObjectSubclass>::Instance);
+        let chain: Borrowed<TlsCertificate> = from_glib_borrow(chain);
+        let identity: Option<Borrowed<SocketConnectable>> =
+            (!identity.is_null()).then(|| from_glib_borrow(identity));
+        instance
+            .imp()
+            .verify_chain(&chain, identity.as_deref())
+            .into_glib()
+    }
+}
+
+// Tools
+
+/// Get host name of the `identity`
+fn host(identity: &SocketConnectable) -> String {
+    match identity.downcast_ref::<NetworkAddress>() {
+        Some(network_address) => network_address.hostname().to_lowercase(),
+        None => identity.to_string().to_lowercase(),
+    }
+}
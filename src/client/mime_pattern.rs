@@ -0,0 +1,50 @@
+//! MIME type patterns matching: exact (`image/png`), type (`image/*`) or any (`*/*`)
+
+/// Match specificity of the exact pattern
+pub const EXACT: u8 = 2;
+/// Match specificity of the type pattern
+pub const TYPE: u8 = 1;
+/// Match specificity of the any pattern
+pub const ANY: u8 = 0;
+
+/// Match `mime` by `pattern`
+/// * return `None` on mismatch, or the match specificity (`EXACT`, `TYPE`, `ANY`),
+///   useful to select the most specific pattern from the list
+/// * parameters (e.g. `; charset=utf-8`) and letter case are ignored
+pub fn matches(pattern: &str, mime: &str) -> Option<u8> {
+    let pattern = essence(pattern);
+    let mime = essence(mime);
+    if pattern == "*/*" || pattern == "*" {
+        return Some(ANY);
+    }
+    if pattern == mime {
+        return Some(EXACT);
+    }
+    match pattern.strip_suffix("/*") {
+        Some(t) if mime.split('/').next() == Some(t) => Some(TYPE),
+        _ => None,
+    }
+}
+
+/// Get lowercase MIME type without parameters
+fn essence(mime: &str) -> String {
+    mime.split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+}
+
+#[test]
+fn test() {
+    assert_eq!(matches("image/png", "image/png"), Some(EXACT));
+    assert_eq!(
+        matches("text/gemini", "Text/Gemini; charset=utf-8"),
+        Some(EXACT)
+    );
+    assert_eq!(matches("image/*", "image/png"), Some(TYPE));
+    assert_eq!(matches("*/*", "image/png"), Some(ANY));
+    assert_eq!(matches("image/*", "text/plain"), None);
+    assert_eq!(matches("image/png", "image/jpeg"), None);
+    assert_eq!(matches("image/*", "imagex/png"), None);
+}
@@ -0,0 +1,173 @@
+pub mod candidate;
+pub mod mismatch;
+pub mod mode;
+pub mod policy;
+pub mod retrust;
+
+pub use candidate::Candidate;
+pub use mismatch::Mismatch;
+pub use mode::Mode;
+pub use policy::Policy;
+pub use retrust::Retrust;
+
+use crate::gio::tls_certificate;
+use gio::TlsCertificate;
+use std::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    rc::Rc,
+};
+
+/// Re-trust decision callback, return `true` to accept the new certificate
+/// * called synchronously, before the handshake continues
+/// * not in use by `KnownHosts` database, see `KnownHosts::retrust`
+pub type OnRetrust = Rc<dyn Fn(&Candidate) -> bool>;
+
+/// TOFU pinning policy, by host
+/// * the mismatched certificate is resolved by the `Retrust` policy, then by the decision callback
+///
+/// ``` rust
+/// use ggemini::client::pinning::{Mode, Pinning};
+///
+/// let mut pinning = Pinning::new(Mode::Certificate);
+/// pinning.set("Geminiprotocol.net", Mode::PublicKey);
+///
+/// assert_eq!(pinning.mode("geminiprotocol.net"), Mode::PublicKey);
+/// assert_eq!(pinning.mode("example.org"), Mode::Certificate);
+/// ```
+#[derive(Clone, Default)]
+pub struct Pinning {
+    on_retrust: Option<OnRetrust>,
+    policy: Policy,
+}
+
+impl Pinning {
+    // Constructors
+
+    /// Create new `Self` with `fallback` mode for all hosts
+    pub fn new(fallback: Mode) -> Self {
+        Self::from_policy(Policy::new(fallback))
+    }
+
+    /// Create new `Self` for `policy` given, without the decision callback
+    pub fn from_policy(policy: Policy) -> Self {
+        Self {
+            on_retrust: None,
+            policy,
+        }
+    }
+
+    // Actions
+
+    /// Verify server `certificate` for `host` by the `pinned` ones
+    /// * apply the `Retrust` policy, then the decision callback, on mismatch
+    /// * return `Mismatch` reason if the certificate is not accepted
+    pub fn verify(
+        &self,
+        host: &str,
+        pinned: &[TlsCertificate],
+        certificate: &TlsCertificate,
+    ) -> Result<(), Mismatch> {
+        let mismatch = match self.policy.verify(host, pinned, certificate) {
+            Ok(()) => return Ok(()),
+            Err(mismatch) => mismatch,
+        };
+        if let Some(ref on_retrust) = self.on_retrust
+            && let Ok(candidate) = Candidate::new(host, mismatch, pinned, certificate)
+            && on_retrust(&candidate)
+        {
+            return Ok(());
+        }
+        Err(mismatch)
+    }
+
+    // Getters
+
+    /// Get pinning `Mode` for given `host`
+    pub fn mode(&self, host: &str) -> Mode {
+        self.policy.mode(host)
+    }
+
+    /// Get re-trust policy
+    pub fn retrust(&self) -> Retrust {
+        self.policy.retrust()
+    }
+
+    /// Get reference to the `Policy`, without the decision callback
+    pub fn policy(&self) -> &Policy {
+        &self.policy
+    }
+
+    // Setters
+
+    /// Set decision `callback` for the mismatched certificate, not accepted by `Retrust` policy
+    /// * return `true` from the `callback` to accept the new certificate
+    pub fn connect_retrust(&mut self, callback: impl Fn(&Candidate) -> bool + 'static) {
+        self.on_retrust = Some(Rc::new(callback))
+    }
+
+    /// Change re-trust policy (`Retrust::Prompt` by default)
+    pub fn set_retrust(&mut self, retrust: Retrust) {
+        self.policy.set_retrust(retrust)
+    }
+
+    /// Set pinning `mode` for given `host`, replace existing one
+    pub fn set(&mut self, host: &str, mode: Mode) {
+        self.policy.set(host, mode)
+    }
+
+    /// Remove `host` rule, to apply the fallback mode
+    pub fn unset(&mut self, host: &str) {
+        self.policy.unset(host)
+    }
+
+    /// Change pinning mode for hosts not listed
+    pub fn set_fallback(&mut self, mode: Mode) {
+        self.policy.set_fallback(mode)
+    }
+}
+
+impl Debug for Pinning {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("Pinning")
+            .field("is_on_retrust", &self.on_retrust.is_some())
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+// Tools
+
+/// Check server `certificate` for the `pinned` ones by `mode`
+/// * return `Mismatch` reason on failure
+pub fn check(
+    mode: Mode,
+    pinned: &[TlsCertificate],
+    certificate: &TlsCertificate,
+) -> Result<(), Mismatch> {
+    let pinned: Vec<Vec<u8>> = pinned
+        .iter()
+        .filter_map(|p| tls_certificate::der(p).ok())
+        .collect();
+    match tls_certificate::der(certificate) {
+        Ok(der) => check_der(mode, &pinned, &der),
+        Err(_) => Err(Mismatch::PublicKey),
+    }
+}
+
+/// Check server certificate `der` data for the `pinned` ones by `mode`
+/// * return `Mismatch` reason on failure
+pub fn check_der(mode: Mode, pinned: &[impl AsRef<[u8]>], der: &[u8]) -> Result<(), Mismatch> {
+    if pinned.iter().any(|p| p.as_ref() == der) {
+        return Ok(());
+    }
+    let spki = tls_certificate::der::spki(der).ok();
+    let is_same_key = spki.is_some()
+        && pinned
+            .iter()
+            .any(|p| tls_certificate::der::spki(p.as_ref()).ok() == spki);
+    match (mode, is_same_key) {
+        (Mode::PublicKey, true) => Ok(()),
+        (Mode::Certificate, true) => Err(Mismatch::Certificate),
+        (_, false) => Err(Mismatch::PublicKey),
+    }
+}
@@ -0,0 +1,37 @@
+use super::Mismatch;
+use crate::gio::tls_certificate::{self, Info};
+use gio::TlsCertificate;
+
+/// Re-trust candidate, passed to the decision callback
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    /// Server host name
+    pub host: String,
+    pub mismatch: Mismatch,
+    /// Metadata of the pinned certificates
+    pub pinned: Vec<Info>,
+    /// Metadata of the new server certificate
+    pub certificate: Info,
+}
+
+impl Candidate {
+    // Constructors
+
+    /// Create new `Self` for the `pinned` certificates and new server `certificate`
+    pub fn new(
+        host: &str,
+        mismatch: Mismatch,
+        pinned: &[TlsCertificate],
+        certificate: &TlsCertificate,
+    ) -> Result<Self, tls_certificate::Error> {
+        Ok(Self {
+            host: host.to_string(),
+            mismatch,
+            pinned: pinned
+                .iter()
+                .map(Info::from_certificate)
+                .collect::<Result<_, _>>()?,
+            certificate: Info::from_certificate(certificate)?,
+        })
+    }
+}
@@ -0,0 +1,23 @@
+use std::fmt::{Display, Formatter, Result};
+
+/// Reason of the pinned certificate mismatch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mismatch {
+    /// The certificate changed, but the public key is the same (e.g. renewed)
+    Certificate,
+    /// The certificate and its public key changed
+    PublicKey,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Certificate => {
+                write!(f, "Server certificate changed, the public key is the same")
+            }
+            Self::PublicKey => {
+                write!(f, "Server certificate and public key changed")
+            }
+        }
+    }
+}
@@ -0,0 +1,9 @@
+/// TOFU pinning mode, compare the server certificate with the pinned one by:
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Whole certificate (`TlsCertificate::is_same`)
+    #[default]
+    Certificate,
+    /// SubjectPublicKeyInfo hash, so the certificate renewal with the same key is accepted
+    PublicKey,
+}
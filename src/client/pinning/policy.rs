@@ -0,0 +1,104 @@
+use super::{Mismatch, Mode, Retrust};
+use gio::{TlsCertificate, prelude::TlsCertificateExt};
+use glib::DateTime;
+
+/// Thread-safe part of the `Pinning` policy: modes by host and the `Retrust` policy,
+/// without the decision callback
+/// * applied by `KnownHosts` database, that verifies the certificates in the TLS thread
+#[derive(Debug, Clone, Default)]
+pub struct Policy {
+    /// Mode for hosts not listed
+    fallback: Mode,
+    /// Host, mode
+    hosts: Vec<(String, Mode)>,
+    retrust: Retrust,
+}
+
+impl Policy {
+    // Constructors
+
+    /// Create new `Self` with `fallback` mode for all hosts
+    pub fn new(fallback: Mode) -> Self {
+        Self {
+            fallback,
+            hosts: Vec::new(),
+            retrust: Retrust::default(),
+        }
+    }
+
+    // Actions
+
+    /// Verify server `certificate` for `host` by the `pinned` ones
+    /// * apply the `Retrust` policy on mismatch
+    /// * return `Mismatch` reason if the certificate is not accepted
+    pub fn verify(
+        &self,
+        host: &str,
+        pinned: &[TlsCertificate],
+        certificate: &TlsCertificate,
+    ) -> Result<(), Mismatch> {
+        let mismatch = match super::check(self.mode(host), pinned, certificate) {
+            Ok(()) => return Ok(()),
+            Err(mismatch) => mismatch,
+        };
+        let is_accepted = match self.retrust {
+            Retrust::Prompt => false,
+            Retrust::Expired => !pinned.is_empty() && pinned.iter().all(is_expired),
+            Retrust::SameKey => mismatch == Mismatch::Certificate,
+        };
+        if is_accepted { Ok(()) } else { Err(mismatch) }
+    }
+
+    // Getters
+
+    /// Get pinning `Mode` for given `host`
+    pub fn mode(&self, host: &str) -> Mode {
+        let host = host.to_lowercase();
+        self.hosts
+            .iter()
+            .find(|(h, _)| *h == host)
+            .map_or(self.fallback, |(_, mode)| *mode)
+    }
+
+    /// Get re-trust policy
+    pub fn retrust(&self) -> Retrust {
+        self.retrust
+    }
+
+    // Setters
+
+    /// Change re-trust policy (`Retrust::Prompt` by default)
+    pub fn set_retrust(&mut self, retrust: Retrust) {
+        self.retrust = retrust
+    }
+
+    /// Set pinning `mode` for given `host`, replace existing one
+    pub fn set(&mut self, host: &str, mode: Mode) {
+        let host = host.trim().to_lowercase();
+        match self.hosts.iter_mut().find(|(h, _)| *h == host) {
+            Some(rule) => rule.1 = mode,
+            None => self.hosts.push((host, mode)),
+        }
+    }
+
+    /// Remove `host` rule, to apply the fallback mode
+    pub fn unset(&mut self, host: &str) {
+        let host = host.trim().to_lowercase();
+        self.hosts.retain(|(h, _)| *h != host)
+    }
+
+    /// Change pinning mode for hosts not listed
+    pub fn set_fallback(&mut self, mode: Mode) {
+        self.fallback = mode
+    }
+}
+
+// Tools
+
+/// Check the certificate validity period is over
+fn is_expired(certificate: &TlsCertificate) -> bool {
+    match (certificate.not_valid_after(), DateTime::now_utc()) {
+        (Some(not_valid_after), Ok(now)) => not_valid_after < now,
+        _ => false,
+    }
+}
@@ -0,0 +1,11 @@
+/// Re-trust policy for the server certificate not matching the pinned ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Retrust {
+    /// Always ask the decision callback (reject, if not connected)
+    #[default]
+    Prompt,
+    /// Accept automatically, if all pinned certificates are expired (the rotation case)
+    Expired,
+    /// Accept automatically, if the public key is unchanged
+    SameKey,
+}
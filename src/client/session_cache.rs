@@ -0,0 +1,188 @@
+pub mod key;
+pub mod sessions;
+
+pub use key::Key;
+pub use sessions::Sessions;
+
+use gio::{
+    MemoryInputStream, MemoryOutputStream, SimpleIOStream, SocketConnectable, TlsClientConnection,
+    prelude::{IOStreamExt, TlsClientConnectionExt, TlsConnectionExt},
+};
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+/// Default max sessions total
+pub const DEFAULT_CAPACITY: usize = 32;
+
+/// Default max session age
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(3600);
+
+/// TLS session cache, isolated by (host, port, identity) `Key`
+/// * replaces the glib-networking cache, that may resume the session
+///   established with another client certificate
+/// * the session state is copied from the last connection handshaken with the same `Key`,
+///   see [copy_session_state](https://docs.gtk.org/gio/method.TlsClientConnection.copy_session_state.html)
+/// * the TLS backend does not report if the session was actually resumed
+/// * clones share the same cache, e.g. between every `Client` in the application
+///
+/// **Session lifetime**
+/// * the session lives until it is replaced by the next handshake with the same `Key`,
+///   evicted as the oldest one on `capacity` overflow, or expired in `max_age`
+/// * the session is evicted on the handshake failure with the same `Key`
+/// * the cached connection is referenced until it is closed, so `capacity`
+///   limits the sockets kept open; the closed connection is replaced
+///   by the detached session state copy, on the next `store` call
+#[derive(Debug, Clone)]
+pub struct SessionCache {
+    sessions: Rc<RefCell<Sessions<TlsClientConnection>>>,
+}
+
+impl Default for SessionCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionCache {
+    // Constructors
+
+    /// Create new empty `Self` with `DEFAULT_CAPACITY` and `DEFAULT_MAX_AGE`
+    pub fn new() -> Self {
+        Self {
+            sessions: Rc::new(RefCell::new(Sessions::new(
+                DEFAULT_CAPACITY,
+                DEFAULT_MAX_AGE,
+            ))),
+        }
+    }
+
+    // Actions
+
+    /// Copy the session state cached for `key` to new (not handshaken) `tls_client_connection`
+    /// * return `true` if the session state was found
+    pub fn resume(&self, key: &Key, tls_client_connection: &TlsClientConnection) -> bool {
+        match self.sessions.borrow_mut().get(key, glib::monotonic_time()) {
+            Some(source) => {
+                tls_client_connection.copy_session_state(source);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cache the session of handshaken `tls_client_connection` for `key`,
+    /// replace existing one, if any
+    /// * the connection without the peer certificate (not handshaken) is not cached
+    /// * detach the session state of the closed connections cached
+    pub fn store(&self, key: Key, tls_client_connection: TlsClientConnection) {
+        if tls_client_connection.peer_certificate().is_none() {
+            return;
+        }
+        let mut sessions = self.sessions.borrow_mut();
+        sessions.retain(|_, connection| {
+            if !connection.is_closed() {
+                return true;
+            }
+            match detach(connection) {
+                Some(session) => {
+                    *connection = session;
+                    true
+                }
+                None => false,
+            }
+        });
+        sessions.insert(key, tls_client_connection, glib::monotonic_time())
+    }
+
+    /// Forget the session for `key`, e.g. on the handshake failure
+    pub fn remove(&self, key: &Key) {
+        self.sessions.borrow_mut().remove(key);
+    }
+
+    /// Forget sessions for given client certificate `identity` (fingerprint),
+    /// e.g. when the identity is deleted; `None` for guest sessions
+    pub fn remove_identity(&self, identity: Option<&str>) {
+        self.sessions
+            .borrow_mut()
+            .retain(|key, _| key.identity.as_deref() != identity)
+    }
+
+    /// Forget sessions for given `host`
+    pub fn remove_host(&self, host: &str) {
+        self.sessions
+            .borrow_mut()
+            .retain(|key, _| !key.host.eq_ignore_ascii_case(host))
+    }
+
+    /// Forget all sessions
+    pub fn clear(&self) {
+        self.sessions.borrow_mut().clear()
+    }
+
+    // Getters
+
+    /// Get sessions total
+    pub fn len(&self) -> usize {
+        self.sessions.borrow().len()
+    }
+
+    /// Check `Self` is empty
+    pub fn is_empty(&self) -> bool {
+        self.sessions.borrow().is_empty()
+    }
+
+    // Setters
+
+    /// Change max sessions total (`DEFAULT_CAPACITY`), forget the oldest ones on overflow
+    pub fn set_capacity(&self, capacity: usize) {
+        self.sessions.borrow_mut().set_capacity(capacity)
+    }
+
+    /// Change max session age (`DEFAULT_MAX_AGE`)
+    pub fn set_max_age(&self, max_age: Duration) {
+        self.sessions.borrow_mut().set_max_age(max_age)
+    }
+}
+
+// Tools
+
+/// Copy the session state of `tls_client_connection` to new connection
+/// over the memory stream, that does not hold the socket
+fn detach(tls_client_connection: &TlsClientConnection) -> Option<TlsClientConnection> {
+    let session = TlsClientConnection::new(
+        &SimpleIOStream::new(
+            &MemoryInputStream::new(),
+            &MemoryOutputStream::new_resizable(),
+        ),
+        None::<&SocketConnectable>,
+    )
+    .ok()?;
+    session.copy_session_state(tls_client_connection);
+    Some(session)
+}
+
+#[test]
+fn test() {
+    use gio::NetworkAddress;
+
+    let address = NetworkAddress::new("Geminiprotocol.net", 1965);
+    let key = Key::new(&address, None).unwrap();
+    assert_eq!(key.host, "geminiprotocol.net");
+    assert_eq!(key.port, 1965);
+    assert_eq!(key.identity, None);
+    assert_ne!(
+        key,
+        Key {
+            identity: Some("ab".into()),
+            ..key.clone()
+        }
+    );
+
+    let cache = SessionCache::new();
+    assert!(cache.is_empty());
+    cache.remove(&key);
+    cache.set_capacity(1);
+    cache.set_max_age(Duration::from_secs(1));
+    cache.remove_identity(None);
+    cache.clear();
+    assert_eq!(cache.len(), 0);
+}
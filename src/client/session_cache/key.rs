@@ -0,0 +1,37 @@
+use crate::gio::tls_certificate;
+use gio::{NetworkAddress, TlsCertificate, prelude::NetworkAddressExt};
+use glib::ChecksumType;
+
+/// TLS session isolation key
+/// * the session is resumed for the same host, port and client certificate
+///   (or the same absence of one) only
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub host: String,
+    pub port: u16,
+    /// SHA-256 fingerprint of the client certificate, `None` for guest session
+    pub identity: Option<String>,
+}
+
+impl Key {
+    // Constructors
+
+    /// Create new `Self` for given `NetworkAddress` and client `certificate`
+    /// * return `None` if the certificate fingerprint is not available,
+    ///   to not mix the identity with the guest session
+    pub fn new(
+        network_address: &NetworkAddress,
+        certificate: Option<&TlsCertificate>,
+    ) -> Option<Self> {
+        Some(Self {
+            host: network_address.hostname().to_lowercase(),
+            port: network_address.port(),
+            identity: match certificate {
+                Some(certificate) => {
+                    Some(tls_certificate::fingerprint(certificate, ChecksumType::Sha256).ok()?)
+                }
+                None => None,
+            },
+        })
+    }
+}
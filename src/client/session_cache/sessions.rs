@@ -0,0 +1,161 @@
+use super::Key;
+use std::{collections::HashMap, time::Duration};
+
+/// Bounded sessions container for `SessionCache`
+/// * the oldest entry is evicted on the `capacity` overflow
+/// * the entry is expired in `max_age` after it was stored
+/// * the time `now` is [monotonic](https://docs.gtk.org/glib/func.get_monotonic_time.html)
+///   in microseconds
+#[derive(Debug)]
+pub struct Sessions<T> {
+    capacity: usize,
+    /// Value, stored at time
+    entries: HashMap<Key, (T, i64)>,
+    max_age: Duration,
+}
+
+impl<T> Sessions<T> {
+    // Constructors
+
+    /// Create new empty `Self`
+    pub fn new(capacity: usize, max_age: Duration) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            max_age,
+        }
+    }
+
+    // Actions
+
+    /// Get value for `key`, if not expired
+    /// * the expired entry is removed
+    pub fn get(&mut self, key: &Key, now: i64) -> Option<&T> {
+        if self
+            .entries
+            .get(key)
+            .is_some_and(|(_, stored)| self.is_expired(*stored, now))
+        {
+            self.entries.remove(key);
+        }
+        self.entries.get(key).map(|(value, _)| value)
+    }
+
+    /// Insert `value` for `key`, replace existing one
+    /// * remove expired entries, then the oldest ones, to keep the `capacity`
+    pub fn insert(&mut self, key: Key, value: T, now: i64) {
+        self.entries.remove(&key);
+        self.entries
+            .retain(|_, (_, stored)| now - *stored < self.max_age.as_micros() as i64);
+        self.shrink(self.capacity.saturating_sub(1));
+        if self.capacity > 0 {
+            self.entries.insert(key, (value, now));
+        }
+    }
+
+    /// Remove value for `key`, if any
+    pub fn remove(&mut self, key: &Key) -> Option<T> {
+        self.entries.remove(key).map(|(value, _)| value)
+    }
+
+    /// Keep the entries for which `f` returns `true` only
+    /// * the value could be replaced by `f`
+    pub fn retain(&mut self, mut f: impl FnMut(&Key, &mut T) -> bool) {
+        self.entries.retain(|key, (value, _)| f(key, value))
+    }
+
+    /// Remove all entries
+    pub fn clear(&mut self) {
+        self.entries.clear()
+    }
+
+    // Getters
+
+    /// Get entries total, including not removed expired ones
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check `Self` is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // Setters
+
+    /// Change max entries total, remove the oldest ones on overflow
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.shrink(capacity)
+    }
+
+    /// Change max age of the entry
+    pub fn set_max_age(&mut self, max_age: Duration) {
+        self.max_age = max_age
+    }
+
+    // Tools
+
+    fn is_expired(&self, stored: i64, now: i64) -> bool {
+        now - stored >= self.max_age.as_micros() as i64
+    }
+
+    /// Remove the oldest entries, to keep `len` ones max
+    fn shrink(&mut self, len: usize) {
+        while self.entries.len() > len {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, stored))| *stored)
+                .map(|(key, _)| key.clone());
+            if let Some(key) = oldest {
+                self.entries.remove(&key);
+            }
+        }
+    }
+}
+
+#[test]
+fn test() {
+    const SECOND: i64 = 1_000_000;
+
+    fn key(host: &str, identity: Option<&str>) -> Key {
+        Key {
+            host: host.into(),
+            port: 1965,
+            identity: identity.map(|i| i.into()),
+        }
+    }
+
+    let mut sessions = Sessions::new(2, Duration::from_secs(10));
+    assert!(sessions.is_empty());
+
+    // store and resume
+    sessions.insert(key("a", None), 1, 0);
+    assert_eq!(sessions.get(&key("a", None), SECOND), Some(&1));
+
+    // the other identity misses
+    assert_eq!(sessions.get(&key("a", Some("ab")), SECOND), None);
+    sessions.insert(key("a", Some("ab")), 2, SECOND);
+    assert_eq!(sessions.get(&key("a", Some("ab")), SECOND), Some(&2));
+    assert_eq!(sessions.get(&key("a", None), SECOND), Some(&1));
+
+    // the oldest is evicted on overflow
+    sessions.insert(key("b", None), 3, 2 * SECOND);
+    assert_eq!(sessions.len(), 2);
+    assert_eq!(sessions.get(&key("a", None), 2 * SECOND), None);
+    assert_eq!(sessions.get(&key("b", None), 2 * SECOND), Some(&3));
+
+    // expired
+    assert_eq!(sessions.get(&key("a", Some("ab")), 11 * SECOND), None);
+    assert_eq!(sessions.len(), 1);
+
+    sessions.retain(|_, value| *value != 3);
+    assert!(sessions.is_empty());
+
+    sessions.insert(key("a", None), 1, 0);
+    sessions.set_capacity(0);
+    assert!(sessions.is_empty());
+    sessions.insert(key("a", None), 1, 0);
+    assert!(sessions.is_empty());
+}
@@ -0,0 +1,86 @@
+use super::mime_pattern;
+
+/// Body size limits policy, by MIME type patterns
+/// * applied by the `Body` helpers after the success header is parsed
+///
+/// ``` rust
+/// use ggemini::client::SizeLimits;
+///
+/// let mut limits = SizeLimits::new(Some(0xfffff)); // 1 MB fallback
+/// limits.set("text/*", Some(0x200000)); // 2 MB
+/// limits.set("image/*", Some(0x1400000)); // 20 MB
+/// limits.set("audio/*", None); // unlimited
+///
+/// assert_eq!(limits.limit("text/gemini"), Some(0x200000));
+/// assert_eq!(limits.limit("audio/ogg"), None);
+/// assert_eq!(limits.limit("application/pdf"), Some(0xfffff));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SizeLimits {
+    /// Limit for MIME types not matching any pattern, `None` for unlimited
+    fallback: Option<usize>,
+    /// Pattern, limit (`None` for unlimited)
+    rules: Vec<(String, Option<usize>)>,
+}
+
+impl SizeLimits {
+    // Constructors
+
+    /// Create new `Self` with `fallback` limit (`None` for unlimited)
+    pub fn new(fallback: Option<usize>) -> Self {
+        Self {
+            fallback,
+            rules: Vec::new(),
+        }
+    }
+
+    // Getters
+
+    /// Get bytes limit for given `mime` type, using the most specific pattern
+    /// * return `None` for unlimited
+    pub fn limit(&self, mime: &str) -> Option<usize> {
+        self.rules
+            .iter()
+            .filter_map(|(pattern, limit)| {
+                mime_pattern::matches(pattern, mime).map(|specificity| (specificity, *limit))
+            })
+            .max_by_key(|(specificity, _)| *specificity)
+            .map_or(self.fallback, |(_, limit)| limit)
+    }
+
+    // Setters
+
+    /// Set `limit` for MIME `pattern`: exact (`image/png`), type (`image/*`) or any (`*/*`)
+    /// * replace existing limit for the same pattern
+    /// * `None` for unlimited
+    pub fn set(&mut self, pattern: &str, limit: Option<usize>) {
+        let pattern = pattern.trim().to_lowercase();
+        match self.rules.iter_mut().find(|(p, _)| *p == pattern) {
+            Some(rule) => rule.1 = limit,
+            None => self.rules.push((pattern, limit)),
+        }
+    }
+
+    /// Set `limit` for MIME types not matching any pattern, `None` for unlimited
+    pub fn set_fallback(&mut self, limit: Option<usize>) {
+        self.fallback = limit
+    }
+}
+
+#[test]
+fn test() {
+    let mut l = SizeLimits::default();
+    assert_eq!(l.limit("text/gemini"), None);
+
+    l.set("*/*", Some(1));
+    l.set("image/*", Some(2));
+    l.set("image/png", Some(3));
+    l.set("audio/*", None);
+    assert_eq!(l.limit("text/gemini"), Some(1));
+    assert_eq!(l.limit("image/jpeg"), Some(2));
+    assert_eq!(l.limit("image/png"), Some(3));
+    assert_eq!(l.limit("audio/ogg"), None);
+
+    l.set("image/png", Some(4));
+    assert_eq!(l.limit("image/png"), Some(4));
+}
@@ -0,0 +1,118 @@
+pub mod phase;
+pub mod timer;
+
+pub use phase::Phase;
+pub use timer::Timer;
+
+use super::DEFAULT_TIMEOUT;
+use std::time::Duration;
+
+/// Independent timeouts for every request phase, `None` to disable
+/// * the `connect`, `handshake`, `header_first_byte`, `header` and `deadline` phases
+///   are enforced by `Timer`, so the slow server can't extend them by trickling bytes
+/// * the `body_idle` phase is applied to the socket as the I/O idle timeout, since the request
+///   write, so it covers the request data (e.g. Titan upload) and any body consumer,
+///   including `Body::into_input_stream` and the direct `Connection::stream` readers
+/// * the `deadline` phase counts from the connect begin to the `Body` complete
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeouts {
+    /// DNS lookup and TCP connect
+    pub connect: Option<Duration>,
+    /// TLS handshake
+    pub handshake: Option<Duration>,
+    /// Time to the first header byte, since the request is sent
+    pub header_first_byte: Option<Duration>,
+    /// Time to the whole header, since the request is sent
+    pub header: Option<Duration>,
+    /// Max time between the body bytes (rounded up to seconds)
+    pub body_idle: Option<Duration>,
+    /// Total request time
+    pub deadline: Option<Duration>,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        let timeout = Some(Duration::from_secs(DEFAULT_TIMEOUT.into()));
+        Self {
+            connect: timeout,
+            handshake: timeout,
+            header_first_byte: timeout,
+            header: timeout,
+            body_idle: timeout,
+            deadline: None,
+        }
+    }
+}
+
+impl Timeouts {
+    // Constructors
+
+    /// Create new `Self` with all phases disabled
+    pub fn none() -> Self {
+        Self {
+            connect: None,
+            handshake: None,
+            header_first_byte: None,
+            header: None,
+            body_idle: None,
+            deadline: None,
+        }
+    }
+
+    // Getters
+
+    /// Get timeout for given `Phase`, `None` if disabled
+    pub fn get(&self, phase: Phase) -> Option<Duration> {
+        match phase {
+            Phase::Connect => self.connect,
+            Phase::Handshake => self.handshake,
+            Phase::HeaderFirstByte => self.header_first_byte,
+            Phase::Header => self.header,
+            Phase::BodyIdle => self.body_idle,
+            Phase::Deadline => self.deadline,
+        }
+    }
+
+    /// Get `body_idle` value in seconds, as expected by the
+    /// [Socket](https://docs.gtk.org/gio/method.Socket.set_timeout.html) (`0` to disable)
+    pub fn body_idle_secs(&self) -> u32 {
+        self.body_idle.map_or(0, |d| {
+            let secs = d.as_secs() + u64::from(d.subsec_nanos() > 0);
+            u32::try_from(secs).unwrap_or(u32::MAX).max(1)
+        })
+    }
+
+    // Setters
+
+    /// Change timeout for given `Phase`, `None` to disable
+    pub fn set(&mut self, phase: Phase, timeout: Option<Duration>) {
+        match phase {
+            Phase::Connect => self.connect = timeout,
+            Phase::Handshake => self.handshake = timeout,
+            Phase::HeaderFirstByte => self.header_first_byte = timeout,
+            Phase::Header => self.header = timeout,
+            Phase::BodyIdle => self.body_idle = timeout,
+            Phase::Deadline => self.deadline = timeout,
+        }
+    }
+}
+
+#[test]
+fn test() {
+    let mut timeouts = Timeouts::none();
+    assert_eq!(timeouts.body_idle_secs(), 0);
+
+    timeouts.set(Phase::BodyIdle, Some(Duration::from_millis(1500)));
+    assert_eq!(timeouts.body_idle_secs(), 2);
+
+    timeouts.set(Phase::BodyIdle, Some(Duration::from_millis(1)));
+    assert_eq!(timeouts.body_idle_secs(), 1);
+
+    timeouts.set(Phase::Header, Some(Duration::from_secs(5)));
+    assert_eq!(timeouts.get(Phase::Header), Some(Duration::from_secs(5)));
+    assert_eq!(timeouts.get(Phase::Deadline), None);
+    assert_eq!(
+        Timeouts::default().get(Phase::Connect),
+        Some(Duration::from_secs(DEFAULT_TIMEOUT.into()))
+    );
+}
@@ -0,0 +1,37 @@
+use std::fmt::{Display, Formatter, Result};
+
+/// Request phase, used to name the expired timeout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Connect,
+    Handshake,
+    HeaderFirstByte,
+    Header,
+    BodyIdle,
+    Deadline,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Connect => {
+                write!(f, "connect")
+            }
+            Self::Handshake => {
+                write!(f, "TLS handshake")
+            }
+            Self::HeaderFirstByte => {
+                write!(f, "header first byte")
+            }
+            Self::Header => {
+                write!(f, "header")
+            }
+            Self::BodyIdle => {
+                write!(f, "body idle")
+            }
+            Self::Deadline => {
+                write!(f, "request deadline")
+            }
+        }
+    }
+}
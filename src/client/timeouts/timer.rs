@@ -0,0 +1,140 @@
+use gio::{
+    Cancellable, CancelledHandlerId,
+    prelude::{CancellableExt, CancellableExtManual},
+};
+use glib::SourceId;
+use std::{
+    cell::{Cell, RefCell},
+    fmt::{Debug, Formatter, Result},
+    rc::Rc,
+    time::Duration,
+};
+
+/// Action to run on `Timer` expire
+type OnExpire = Rc<RefCell<Option<Box<dyn FnOnce()>>>>;
+
+/// Cancel child [Cancellable](https://docs.gtk.org/gio/class.Cancellable.html) on timeout
+/// * the child is also cancelled with its parent (e.g. by user)
+/// * the timer source is removed, and the parent handler disconnected, on drop
+pub struct Timer {
+    cancellable: Cancellable,
+    is_expired: Rc<Cell<bool>>,
+    on_expire: OnExpire,
+    parent: (Cancellable, Option<CancelledHandlerId>),
+    source: Rc<RefCell<Option<SourceId>>>,
+}
+
+impl Timer {
+    // Constructors
+
+    /// Create new `Self` for `parent` cancellable, start the `timeout` (if any)
+    pub fn new(parent: &Cancellable, timeout: Option<Duration>) -> Self {
+        let cancellable = Cancellable::new();
+        let handler = parent.connect_cancelled_local({
+            let cancellable = cancellable.clone();
+            move |_| cancellable.cancel()
+        });
+        let this = Self {
+            cancellable,
+            is_expired: Rc::new(Cell::new(false)),
+            on_expire: Rc::new(RefCell::new(None)),
+            parent: (parent.clone(), handler),
+            source: Rc::new(RefCell::new(None)),
+        };
+        if let Some(timeout) = timeout {
+            this.source
+                .replace(Some(glib::timeout_add_local_once(timeout, {
+                    let cancellable = this.cancellable.clone();
+                    let is_expired = this.is_expired.clone();
+                    let on_expire = this.on_expire.clone();
+                    let source = this.source.clone();
+                    move || {
+                        // the source is removed by glib, so forget it
+                        source.take();
+                        if cancellable.is_cancelled() {
+                            return;
+                        }
+                        is_expired.set(true);
+                        if let Some(callback) = on_expire.take() {
+                            callback()
+                        }
+                        cancellable.cancel()
+                    }
+                })));
+        }
+        this
+    }
+
+    // Actions
+
+    /// Stop the timeout, keep following the parent cancellable
+    /// * e.g. when the phase is complete, but the `Cancellable` is still in use
+    pub fn stop(&self) {
+        if let Some(source) = self.source.take() {
+            source.remove()
+        }
+    }
+
+    /// Run `callback` once, when `Self` expires
+    /// * the `callback` runs before the `Cancellable` is cancelled
+    pub fn connect_expired(&self, callback: impl FnOnce() + 'static) {
+        self.on_expire.replace(Some(Box::new(callback)));
+    }
+
+    // Getters
+
+    /// Get child `Cancellable` to pass into the phase operations
+    pub fn cancellable(&self) -> &Cancellable {
+        &self.cancellable
+    }
+
+    /// Check `Self` timeout is expired (not cancelled by parent)
+    pub fn is_expired(&self) -> bool {
+        self.is_expired.get()
+    }
+}
+
+impl Debug for Timer {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        f.debug_struct("Timer")
+            .field("is_expired", &self.is_expired.get())
+            .field("is_active", &self.source.borrow().is_some())
+            .finish()
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        self.stop();
+        if let Some(handler) = self.parent.1.take() {
+            self.parent.0.disconnect_cancelled(handler)
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use glib::MainContext;
+
+    let parent = Cancellable::new();
+
+    // expire
+    let timer = Timer::new(&parent, Some(Duration::from_millis(1)));
+    let is_called = Rc::new(Cell::new(false));
+    timer.connect_expired({
+        let is_called = is_called.clone();
+        move || is_called.set(true)
+    });
+    while !timer.cancellable().is_cancelled() {
+        MainContext::default().iteration(true);
+    }
+    assert!(timer.is_expired());
+    assert!(is_called.get());
+    assert!(!parent.is_cancelled());
+
+    // cancel by parent
+    let timer = Timer::new(&parent, None);
+    parent.cancel();
+    assert!(timer.cancellable().is_cancelled());
+    assert!(!timer.is_expired());
+}
@@ -0,0 +1,165 @@
+pub mod mark;
+
+pub use mark::Mark;
+
+use std::{
+    fmt::{Display, Formatter, Result},
+    time::Duration,
+};
+
+/// Request timing breakdown, by the [monotonic clock](https://docs.gtk.org/glib/func.get_monotonic_time.html)
+/// * all values are in microseconds
+/// * the mark is `None` when the request did not reach it (e.g. failed before)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    /// Request begin
+    pub start: i64,
+    pub resolved: Option<i64>,
+    pub connected: Option<i64>,
+    pub handshaken: Option<i64>,
+    pub request_sent: Option<i64>,
+    pub header_first_byte: Option<i64>,
+    pub header_complete: Option<i64>,
+    pub body_complete: Option<i64>,
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Timing {
+    // Constructors
+
+    /// Create new `Self`, begin from the current time
+    pub fn new() -> Self {
+        Self::from_start(glib::monotonic_time())
+    }
+
+    /// Create new `Self`, begin from given `start` time
+    pub fn from_start(start: i64) -> Self {
+        Self {
+            start,
+            resolved: None,
+            connected: None,
+            handshaken: None,
+            request_sent: None,
+            header_first_byte: None,
+            header_complete: None,
+            body_complete: None,
+        }
+    }
+
+    // Actions
+
+    /// Record given `Mark` by the current time
+    pub fn mark(&mut self, mark: Mark) {
+        self.set(mark, glib::monotonic_time())
+    }
+
+    // Getters
+
+    /// Get time recorded for given `Mark`
+    pub fn get(&self, mark: Mark) -> Option<i64> {
+        match mark {
+            Mark::Resolved => self.resolved,
+            Mark::Connected => self.connected,
+            Mark::Handshaken => self.handshaken,
+            Mark::RequestSent => self.request_sent,
+            Mark::HeaderFirstByte => self.header_first_byte,
+            Mark::HeaderComplete => self.header_complete,
+            Mark::BodyComplete => self.body_complete,
+        }
+    }
+
+    /// Get time elapsed from the request begin to given `Mark`
+    pub fn elapsed(&self, mark: Mark) -> Option<Duration> {
+        self.get(mark).map(|time| duration(self.start, time))
+    }
+
+    /// Get time spent on given `Mark` step,
+    /// since the previous mark recorded (or the request begin)
+    /// * useful to find the slow step, e.g. DNS, TCP, TLS or the server
+    pub fn step(&self, mark: Mark) -> Option<Duration> {
+        let time = self.get(mark)?;
+        let previous = Mark::ALL
+            .iter()
+            .take_while(|m| **m != mark)
+            .filter_map(|m| self.get(*m))
+            .last()
+            .unwrap_or(self.start);
+        Some(duration(previous, time))
+    }
+
+    // Setters
+
+    /// Set `time` for given `Mark`
+    pub fn set(&mut self, mark: Mark, time: i64) {
+        let time = Some(time);
+        match mark {
+            Mark::Resolved => self.resolved = time,
+            Mark::Connected => self.connected = time,
+            Mark::Handshaken => self.handshaken = time,
+            Mark::RequestSent => self.request_sent = time,
+            Mark::HeaderFirstByte => self.header_first_byte = time,
+            Mark::HeaderComplete => self.header_complete = time,
+            Mark::BodyComplete => self.body_complete = time,
+        }
+    }
+}
+
+impl Display for Timing {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let mut steps = Mark::ALL
+            .iter()
+            .filter_map(|mark| self.step(*mark).map(|step| (mark, step)))
+            .peekable();
+        if steps.peek().is_none() {
+            return write!(f, "no timing marks");
+        }
+        for (i, (mark, step)) in steps.enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{mark}: {}ms", step.as_millis())?;
+        }
+        Ok(())
+    }
+}
+
+// Tools
+
+/// Get `Duration` between two monotonic times (microseconds)
+fn duration(from: i64, to: i64) -> Duration {
+    Duration::from_micros(to.saturating_sub(from).max(0) as u64)
+}
+
+#[test]
+fn test() {
+    let mut timing = Timing::from_start(1_000);
+    assert_eq!(timing.to_string(), "no timing marks");
+
+    timing.set(Mark::Resolved, 3_000);
+    timing.set(Mark::Connected, 10_000);
+    // `Handshaken` is skipped, e.g. on failure
+    timing.set(Mark::RequestSent, 25_000);
+
+    assert_eq!(
+        timing.elapsed(Mark::Connected),
+        Some(Duration::from_micros(9_000))
+    );
+    assert_eq!(
+        timing.step(Mark::Resolved),
+        Some(Duration::from_micros(2_000))
+    );
+    assert_eq!(
+        timing.step(Mark::RequestSent),
+        Some(Duration::from_micros(15_000))
+    );
+    assert_eq!(timing.step(Mark::Handshaken), None);
+    assert_eq!(
+        timing.to_string(),
+        "resolve: 2ms, connect: 7ms, request: 15ms"
+    );
+}
@@ -0,0 +1,61 @@
+use std::fmt::{Display, Formatter, Result};
+
+/// Request timing mark, in order of the request lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    /// DNS lookup complete
+    Resolved,
+    /// TCP connection established
+    Connected,
+    /// TLS handshake complete
+    Handshaken,
+    /// All request bytes (including Titan data) sent
+    RequestSent,
+    /// First response header byte received
+    HeaderFirstByte,
+    /// Response header complete
+    HeaderComplete,
+    /// Response body complete (by the `Body` helpers)
+    BodyComplete,
+}
+
+impl Mark {
+    /// All marks, in order of the request lifecycle
+    pub const ALL: [Self; 7] = [
+        Self::Resolved,
+        Self::Connected,
+        Self::Handshaken,
+        Self::RequestSent,
+        Self::HeaderFirstByte,
+        Self::HeaderComplete,
+        Self::BodyComplete,
+    ];
+}
+
+impl Display for Mark {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Resolved => {
+                write!(f, "resolve")
+            }
+            Self::Connected => {
+                write!(f, "connect")
+            }
+            Self::Handshaken => {
+                write!(f, "handshake")
+            }
+            Self::RequestSent => {
+                write!(f, "request")
+            }
+            Self::HeaderFirstByte => {
+                write!(f, "first byte")
+            }
+            Self::HeaderComplete => {
+                write!(f, "header")
+            }
+            Self::BodyComplete => {
+                write!(f, "body")
+            }
+        }
+    }
+}
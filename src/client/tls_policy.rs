@@ -0,0 +1,90 @@
+pub mod close_notify;
+pub mod error;
+
+pub use close_notify::CloseNotify;
+pub use error::Error;
+
+use super::DEFAULT_SESSION_RESUMPTION;
+use gio::TlsProtocolVersion;
+
+/// TLS security policy, applied to every `Connection`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TlsPolicy {
+    /// Minimum protocol version, checked after the handshake
+    /// * [Gemini specification](https://geminiprotocol.net/docs/protocol-specification.gmi#tls-version)
+    ///   requires TLS 1.2 or newer
+    pub min_version: TlsProtocolVersion,
+    pub close_notify: CloseNotify,
+    /// Allow session resumption, isolated by `SessionCache` of the `Client`
+    /// * [Gemini specification](https://geminiprotocol.net/docs/protocol-specification.gmi#client-certificates)
+    /// * [GnuTLS manual](https://www.gnutls.org/manual/html_node/Session-resumption.html)
+    pub is_session_resumption: bool,
+}
+
+impl Default for TlsPolicy {
+    fn default() -> Self {
+        Self {
+            min_version: TlsProtocolVersion::Tls12,
+            close_notify: CloseNotify::default(),
+            is_session_resumption: DEFAULT_SESSION_RESUMPTION,
+        }
+    }
+}
+
+impl TlsPolicy {
+    // Getters
+
+    /// Check negotiated protocol `version` by `min_version`
+    /// * `TlsProtocolVersion::Unknown` (e.g. not reported by the TLS backend) is below
+    ///   any minimum, so the check fails closed
+    /// * any version is allowed, if `min_version` is not comparable (e.g. `Unknown`)
+    pub fn check_version(&self, version: TlsProtocolVersion) -> Result<(), Error> {
+        match (rank(version), rank(self.min_version)) {
+            (Some(v), Some(min)) if v >= min => Ok(()),
+            (_, None) => Ok(()),
+            _ => Err(Error::ProtocolVersion(version, self.min_version)),
+        }
+    }
+}
+
+// Tools
+
+/// Get comparable rank of the TLS `version`, `None` for unsupported (e.g. DTLS)
+fn rank(version: TlsProtocolVersion) -> Option<u8> {
+    match version {
+        TlsProtocolVersion::Ssl30 => Some(0),
+        TlsProtocolVersion::Tls10 => Some(1),
+        TlsProtocolVersion::Tls11 => Some(2),
+        TlsProtocolVersion::Tls12 => Some(3),
+        TlsProtocolVersion::Tls13 => Some(4),
+        _ => None,
+    }
+}
+
+#[test]
+fn test() {
+    let policy = TlsPolicy::default();
+    assert!(policy.check_version(TlsProtocolVersion::Tls13).is_ok());
+    assert!(policy.check_version(TlsProtocolVersion::Tls12).is_ok());
+    assert_eq!(
+        policy.check_version(TlsProtocolVersion::Unknown),
+        Err(Error::ProtocolVersion(
+            TlsProtocolVersion::Unknown,
+            TlsProtocolVersion::Tls12
+        ))
+    );
+    assert_eq!(
+        policy.check_version(TlsProtocolVersion::Tls11),
+        Err(Error::ProtocolVersion(
+            TlsProtocolVersion::Tls11,
+            TlsProtocolVersion::Tls12
+        ))
+    );
+    assert!(policy.check_version(TlsProtocolVersion::Dtls12).is_err());
+
+    let policy = TlsPolicy {
+        min_version: TlsProtocolVersion::Unknown,
+        ..TlsPolicy::default()
+    };
+    assert!(policy.check_version(TlsProtocolVersion::Unknown).is_ok());
+}
@@ -0,0 +1,14 @@
+/// TLS `close_notify` handling on the body end
+/// * [Gemini specification](https://geminiprotocol.net/docs/protocol-specification.gmi#closing-connections)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CloseNotify {
+    /// Return the typed `Truncated` error (with all bytes received) on the missing `close_notify`,
+    /// as the body may be truncated
+    #[default]
+    Required,
+    /// Complete the body on the missing `close_notify`,
+    /// mark the `Connection` to notify the user
+    Reported,
+    /// Complete the body on the missing `close_notify` silently
+    Ignored,
+}
@@ -0,0 +1,20 @@
+use gio::TlsProtocolVersion;
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    ProtocolVersion(TlsProtocolVersion, TlsProtocolVersion),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::ProtocolVersion(version, min) => {
+                write!(
+                    f,
+                    "TLS protocol version {version:?} is lower than required {min:?}"
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+pub mod verification;
+pub use verification::Verification;
+
+use crate::client::pinning::Pinning;
+use gio::{
+    SocketConnectable, TlsBackend, TlsCertificate, TlsCertificateFlags, TlsDatabaseVerifyFlags,
+    TlsInteraction,
+    prelude::{TlsBackendExt, TlsDatabaseExt},
+};
+
+/// Server certificate validation mode
+/// * the CA chain is validated by the system trust store
+///   ([TlsDatabase](https://docs.gtk.org/gio/class.TlsDatabase.html)) with the SNI host name
+/// * the TOFU check is applied to the `server_certificates` of the request, by `Pinning` policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Validation {
+    /// TOFU check only, the CA chain is ignored
+    #[default]
+    Tofu,
+    /// CA chain only, the `server_certificates` are ignored
+    Ca,
+    /// Valid CA chain or TOFU check passed (e.g. for the self-signed capsules)
+    CaOrTofu,
+    /// Valid CA chain and TOFU check passed
+    CaAndTofu,
+}
+
+impl Validation {
+    // Getters
+
+    /// Check the mode requires the CA chain validation
+    pub fn is_ca(&self) -> bool {
+        !matches!(self, Self::Tofu)
+    }
+
+    /// Check the mode requires the TOFU check
+    pub fn is_tofu(&self) -> bool {
+        !matches!(self, Self::Ca)
+    }
+}
+
+// Tools
+
+/// Verify server `certificate` for `host` by the `validation` mode
+/// * `identity` is the SNI for the CA chain validation
+/// * `pinned` is `None` to accept any certificate by TOFU (the first visit)
+pub fn verify(
+    validation: Validation,
+    pinning: &Pinning,
+    host: &str,
+    identity: Option<&SocketConnectable>,
+    pinned: Option<&[TlsCertificate]>,
+    certificate: &TlsCertificate,
+) -> Verification {
+    let ca = validation.is_ca().then(|| ca(identity, certificate));
+    let is_ca_valid = ca.is_some_and(|flags| flags.is_empty());
+    let tofu = match pinned {
+        // skip the TOFU check (and re-trust prompt) when it's not required
+        Some(pinned)
+            if validation.is_tofu() && !(validation == Validation::CaOrTofu && is_ca_valid) =>
+        {
+            Some(pinning.verify(host, pinned, certificate))
+        }
+        _ => None,
+    };
+    let is_tofu_valid = tofu.is_none_or(|result| result.is_ok());
+    Verification {
+        ca,
+        tofu,
+        is_accepted: match validation {
+            Validation::Tofu => is_tofu_valid,
+            Validation::Ca => is_ca_valid,
+            Validation::CaOrTofu => is_ca_valid || is_tofu_valid,
+            Validation::CaAndTofu => is_ca_valid && is_tofu_valid,
+        },
+    }
+}
+
+/// Validate the CA chain of `certificate` for `identity` by the system trust store
+fn ca(identity: Option<&SocketConnectable>, certificate: &TlsCertificate) -> TlsCertificateFlags {
+    match TlsBackend::default().default_database().verify_chain(
+        certificate,
+        gio::TLS_DATABASE_PURPOSE_AUTHENTICATE_SERVER,
+        identity,
+        None::<&TlsInteraction>,
+        TlsDatabaseVerifyFlags::NONE,
+        None::<&gio::Cancellable>,
+    ) {
+        Ok(flags) => flags,
+        Err(_) => TlsCertificateFlags::GENERIC_ERROR,
+    }
+}
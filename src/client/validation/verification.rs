@@ -0,0 +1,29 @@
+use crate::client::pinning::Mismatch;
+use gio::TlsCertificateFlags;
+
+/// Server certificate verification result, recorded by the handshake
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Verification {
+    /// CA chain validation errors (empty on success), `None` if not checked
+    pub ca: Option<TlsCertificateFlags>,
+    /// TOFU check result, `None` if not checked (or no `server_certificates` pinned)
+    pub tofu: Option<Result<(), Mismatch>>,
+    pub is_accepted: bool,
+}
+
+impl Verification {
+    // Getters
+
+    /// Check the CA chain is valid
+    pub fn is_ca_valid(&self) -> bool {
+        self.ca.is_some_and(|flags| flags.is_empty())
+    }
+
+    /// Get TOFU `Mismatch` reason, if any
+    pub fn mismatch(&self) -> Option<Mismatch> {
+        match self.tofu {
+            Some(Err(mismatch)) => Some(mismatch),
+            _ => None,
+        }
+    }
+}
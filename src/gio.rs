@@ -1,3 +1,13 @@
+pub(crate) mod async_input_stream;
+pub mod bounded_input_stream;
+pub mod content_type;
+pub mod file_download;
 pub mod file_output_stream;
+pub mod identity;
+pub mod lines;
 pub mod memory_input_stream;
 pub mod network_address;
+pub mod output_stream;
+pub mod subprocess;
+pub mod tls_certificate;
+pub mod utf8_decoder;
@@ -0,0 +1,145 @@
+//! Shared tools for the [InputStream](https://docs.gtk.org/gio/class.InputStream.html)
+//! subclasses of this crate (e.g. `BoundedInputStream`)
+//! * the async methods are implemented by the base stream async methods,
+//!   so the default GIO implementation (that calls `read` in the separated thread) is not in use
+
+use gio::{Cancellable, IOStream, InputStream, ffi};
+use glib::{
+    Bytes, Priority,
+    object::{Cast, IsA},
+    subclass::prelude::*,
+    translate::{Borrowed, FromGlib, FromGlibPtrBorrow, IntoGlibPtr},
+};
+use std::ffi::{c_int, c_void};
+
+/// Base stream references holder for the subclass implementation
+/// * the holder is required, as GIO requires the subclass implementation to be `Send`
+/// * `Send` is implemented for the base stream types of this crate subclasses only
+pub struct Source<T>(pub T);
+
+// SAFETY:
+// * the async methods are overridden by `override_async`, so GIO never moves the stream
+//   to the worker thread by itself, and the base stream is used on the main context only
+// * the sync methods still could be called from other thread by the external consumer
+//   (e.g. `GdkPixbuf` loader runs them in the thread pool); the GIO streams support use
+//   from any thread, while the operations are not concurrent, and the holder is always
+//   accessed under the `Mutex` of the implementation, that serializes them
+// * the GObject reference counting is atomic, so the holder can be dropped in any thread
+unsafe impl Send for Source<InputStream> {}
+
+// SAFETY: see `Source<InputStream>`, the same applies to the GIO connections
+unsafe impl Send for Source<IOStream> {}
+
+/// Async methods of the `InputStream` subclass, installed by `override_async`
+pub trait ReadAsync: ObjectSubclass<Type: IsA<InputStream>> {
+    /// Asynchronously read up to `len` bytes
+    fn read_async(
+        &self,
+        len: usize,
+        priority: Priority,
+        cancellable: Option<&Cancellable>,
+        callback: impl FnOnce(Result<Bytes, glib::Error>) + 'static,
+    );
+
+    /// Asynchronously close the base stream
+    fn close_async(
+        &self,
+        priority: Priority,
+        cancellable: Option<&Cancellable>,
+        callback: impl FnOnce(Result<(), glib::Error>) + 'static,
+    );
+}
+
+/// Install `ReadAsync` implementation of `T` to the class vfuncs
+/// * call from `ObjectSubclass::class_init`
+pub fn override_async<T: ReadAsync>(class: &mut T::Class) {
+    // SAFETY: the subclass class struct begins with the parent `GInputStreamClass`
+    let class = unsafe { &mut *(class as *mut T::Class as *mut ffi::GInputStreamClass) };
+    class.read_async = Some(read_async::<T>);
+    class.read_finish = Some(read_finish);
+    class.close_async = Some(close_async::<T>);
+    class.close_finish = Some(close_finish);
+}
+
+// Tools
+
+/// Get implementation `T` of the `stream`
+unsafe fn imp<T: ReadAsync>(stream: &Borrowed<InputStream>) -> &T {
+    T::from_obj(unsafe { stream.unsafe_cast_ref::<T::Type>() })
+}
+
+unsafe extern "C" fn read_async<T: ReadAsync>(
+    stream: *mut ffi::GInputStream,
+    buffer: *mut u8,
+    count: usize,
+    io_priority: c_int,
+    cancellable: *mut ffi::GCancellable,
+    callback: ffi::GAsyncReadyCallback,
+    user_data: *mut c_void,
+) {
+    unsafe {
+        let task = ffi::g_task_new(stream as *mut _, cancellable, callback, user_data);
+        let stream: Borrowed<InputStream> = FromGlibPtrBorrow::from_glib_borrow(stream);
+        let cancellable: Borrowed<Option<Cancellable>> =
+            FromGlibPtrBorrow::from_glib_borrow(cancellable);
+        imp::<T>(&stream).read_async(
+            count,
+            Priority::from_glib(io_priority),
+            cancellable.as_ref().as_ref(),
+            move |result| {
+                match result {
+                    Ok(bytes) => {
+                        // the buffer is valid until the task callback, by the GIO contract
+                        let len = bytes.len().min(count);
+                        std::ptr::copy_nonoverlapping(bytes[..len].as_ptr(), buffer, len);
+                        ffi::g_task_return_int(task, len as isize)
+                    }
+                    Err(e) => ffi::g_task_return_error(task, e.into_glib_ptr()),
+                }
+                glib::gobject_ffi::g_object_unref(task as *mut _)
+            },
+        )
+    }
+}
+
+unsafe extern "C" fn read_finish(
+    _stream: *mut ffi::GInputStream,
+    result: *mut ffi::GAsyncResult,
+    error: *mut *mut glib::ffi::GError,
+) -> isize {
+    unsafe { ffi::g_task_propagate_int(result as *mut ffi::GTask, error) }
+}
+
+unsafe extern "C" fn close_async<T: ReadAsync>(
+    stream: *mut ffi::GInputStream,
+    io_priority: c_int,
+    cancellable: *mut ffi::GCancellable,
+    callback: ffi::GAsyncReadyCallback,
+    user_data: *mut c_void,
+) {
+    unsafe {
+        let task = ffi::g_task_new(stream as *mut _, cancellable, callback, user_data);
+        let stream: Borrowed<InputStream> = FromGlibPtrBorrow::from_glib_borrow(stream);
+        let cancellable: Borrowed<Option<Cancellable>> =
+            FromGlibPtrBorrow::from_glib_borrow(cancellable);
+        imp::<T>(&stream).close_async(
+            Priority::from_glib(io_priority),
+            cancellable.as_ref().as_ref(),
+            move |result| {
+                match result {
+                    Ok(()) => ffi::g_task_return_boolean(task, glib::ffi::GTRUE),
+                    Err(e) => ffi::g_task_return_error(task, e.into_glib_ptr()),
+                }
+                glib::gobject_ffi::g_object_unref(task as *mut _)
+            },
+        )
+    }
+}
+
+unsafe extern "C" fn close_finish(
+    _stream: *mut ffi::GInputStream,
+    result: *mut ffi::GAsyncResult,
+    error: *mut *mut glib::ffi::GError,
+) -> glib::ffi::gboolean {
+    unsafe { ffi::g_task_propagate_boolean(result as *mut ffi::GTask, error) }
+}
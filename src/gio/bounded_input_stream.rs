@@ -0,0 +1,137 @@
+pub mod error;
+mod imp;
+
+pub use error::Error;
+
+use gio::{IOStream, InputStream, prelude::IOStreamExt};
+use glib::{
+    Object,
+    object::{Cast, IsA},
+    subclass::prelude::ObjectSubclassIsExt,
+};
+use std::sync::Arc;
+
+glib::wrapper! {
+    /// [InputStream](https://docs.gtk.org/gio/class.InputStream.html) wrapper,
+    /// that limits bytes total of the base stream
+    ///
+    /// **Useful for**
+    /// * safe read (of memory overflow) by any GIO consumer (e.g. `GdkPixbuf` or media decoders)
+    /// * calculate bytes processed for the progress indication
+    ///
+    /// The base stream is never read past the limit by more than one byte
+    ///
+    /// Return `glib::Error` of `Error` domain on limit reached
+    pub struct BoundedInputStream(ObjectSubclass<imp::BoundedInputStream>) @extends InputStream;
+}
+
+impl BoundedInputStream {
+    // Constructors
+
+    /// Create new `Self` for given `InputStream`
+    /// * `limit` is max bytes total, `None` for unlimited
+    pub fn new(base_stream: &impl IsA<InputStream>, limit: Option<usize>) -> Self {
+        let this: Self = Object::new();
+        this.imp().init(base_stream.clone().upcast(), None, limit);
+        this
+    }
+
+    /// Create new `Self` for given [IOStream](https://docs.gtk.org/gio/class.IOStream.html)
+    /// * owns the `IOStream` reference to keep `Connection` active
+    /// * `limit` is max bytes total, `None` for unlimited
+    pub fn from_stream(io_stream: &impl IsA<IOStream>, limit: Option<usize>) -> Self {
+        let this: Self = Object::new();
+        this.imp().init(
+            io_stream.input_stream(),
+            Some(io_stream.clone().upcast()),
+            limit,
+        );
+        this
+    }
+
+    // Actions
+
+    /// Run `callback` with bytes total on every chunk read from the base stream
+    /// * the `callback` runs in the thread of the read operation: the main context
+    ///   for async methods, or the worker thread for sync ones (e.g. by `GdkPixbuf` loader),
+    ///   so use `glib::MainContext::invoke` to update the UI
+    pub fn connect_progress(&self, callback: impl Fn(usize) + Send + Sync + 'static) {
+        self.imp().connect_progress(Arc::new(callback))
+    }
+
+    // Getters
+
+    /// Get bytes total read from the base stream
+    pub fn total(&self) -> usize {
+        self.imp().total()
+    }
+
+    /// Get bytes total limit, `None` for unlimited
+    pub fn limit(&self) -> Option<usize> {
+        self.imp().limit()
+    }
+}
+
+#[test]
+fn test() {
+    use gio::{MemoryInputStream, prelude::InputStreamExt};
+    use glib::Bytes;
+    use std::{
+        cell::Cell,
+        rc::Rc,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    let s = BoundedInputStream::new(&MemoryInputStream::from_bytes(&Bytes::from(b"abcd")), None);
+    assert_eq!(
+        &*s.read_bytes(0x10000, gio::Cancellable::NONE).unwrap(),
+        b"abcd"
+    );
+    assert_eq!(s.total(), 4);
+
+    let s = BoundedInputStream::new(
+        &MemoryInputStream::from_bytes(&Bytes::from(b"abcd")),
+        Some(3),
+    );
+    assert_eq!(&*s.read_bytes(2, gio::Cancellable::NONE).unwrap(), b"ab");
+    assert!(
+        s.read_bytes(2, gio::Cancellable::NONE)
+            .is_err_and(|e| e.matches(Error::BytesTotal))
+    );
+    assert_eq!(s.total(), 4);
+    assert_eq!(s.limit(), Some(3));
+
+    // clamp the read to the limit
+    let s = BoundedInputStream::new(
+        &MemoryInputStream::from_bytes(&Bytes::from(&[0u8; 100])),
+        Some(3),
+    );
+    assert!(
+        s.read_bytes(0x10000, gio::Cancellable::NONE)
+            .is_err_and(|e| e.matches(Error::BytesTotal))
+    );
+    assert_eq!(s.total(), 4);
+
+    // async read with progress
+    let s = BoundedInputStream::new(
+        &MemoryInputStream::from_bytes(&Bytes::from(b"abcd")),
+        Some(4),
+    );
+    let progress = Arc::new(AtomicUsize::new(0));
+    s.connect_progress({
+        let progress = progress.clone();
+        move |total| progress.store(total, Ordering::Relaxed)
+    });
+    let is_complete = Rc::new(Cell::new(false));
+    s.read_bytes_async(0x10000, glib::Priority::DEFAULT, gio::Cancellable::NONE, {
+        let is_complete = is_complete.clone();
+        move |result| {
+            assert_eq!(&*result.unwrap(), b"abcd");
+            is_complete.set(true)
+        }
+    });
+    while !is_complete.get() {
+        glib::MainContext::default().iteration(true);
+    }
+    assert_eq!(progress.load(Ordering::Relaxed), 4);
+}
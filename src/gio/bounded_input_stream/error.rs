@@ -0,0 +1,8 @@
+/// [Error](https://docs.gtk.org/glib/struct.Error.html) domain of `BoundedInputStream`
+/// * use `glib::Error::matches` or `glib::Error::kind` to detect it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, glib::ErrorDomain)]
+#[error_domain(name = "ggemini-bounded-input-stream-error-quark")]
+pub enum Error {
+    /// Bytes total limit reached
+    BytesTotal,
+}
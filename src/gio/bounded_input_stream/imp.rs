@@ -0,0 +1,178 @@
+use crate::gio::async_input_stream::{ReadAsync, Source, override_async};
+use gio::{
+    Cancellable, IOStream, InputStream,
+    prelude::{IOStreamExt, InputStreamExt, InputStreamExtManual},
+    subclass::prelude::*,
+};
+use glib::{Bytes, Priority};
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicUsize, Ordering},
+};
+
+/// Progress callback with bytes total
+pub type OnProgress = Arc<dyn Fn(usize) + Send + Sync>;
+
+/// Base streams of the `BoundedInputStream`
+struct Base {
+    input_stream: Source<InputStream>,
+    io_stream: Option<Source<IOStream>>,
+}
+
+#[derive(Default)]
+pub struct BoundedInputStream {
+    base: Mutex<Option<Base>>,
+    limit: Mutex<Option<usize>>,
+    on_progress: Mutex<Option<OnProgress>>,
+    total: AtomicUsize,
+}
+
+impl BoundedInputStream {
+    pub fn init(
+        &self,
+        input_stream: InputStream,
+        io_stream: Option<IOStream>,
+        limit: Option<usize>,
+    ) {
+        self.base.lock().unwrap().replace(Base {
+            input_stream: Source(input_stream),
+            io_stream: io_stream.map(Source),
+        });
+        *self.limit.lock().unwrap() = limit;
+    }
+
+    pub fn connect_progress(&self, callback: OnProgress) {
+        self.on_progress.lock().unwrap().replace(callback);
+    }
+
+    pub fn total(&self) -> usize {
+        self.total.load(Ordering::Relaxed)
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        *self.limit.lock().unwrap()
+    }
+
+    /// Clamp the read `len` to the bytes left by the limit,
+    /// plus one byte to detect the limit reached
+    fn clamp(&self, len: usize) -> Result<usize, glib::Error> {
+        match self.limit() {
+            Some(limit) => {
+                let total = self.total();
+                if total > limit {
+                    return Err(limit_error(total, limit));
+                }
+                Ok(len.min(limit - total + 1))
+            }
+            None => Ok(len),
+        }
+    }
+
+    /// Count `len` bytes read, check the limit, then report the progress
+    fn count(&self, len: usize) -> Result<usize, glib::Error> {
+        let total = self.total.fetch_add(len, Ordering::Relaxed) + len;
+        if let Some(limit) = self.limit()
+            && total > limit
+        {
+            return Err(limit_error(total, limit));
+        }
+        let on_progress = self.on_progress.lock().unwrap().clone();
+        if let Some(callback) = on_progress
+            && len > 0
+        {
+            callback(total)
+        }
+        Ok(len)
+    }
+}
+
+#[glib::object_subclass]
+impl ObjectSubclass for BoundedInputStream {
+    const NAME: &'static str = "GGeminiBoundedInputStream";
+    type Type = super::BoundedInputStream;
+    type ParentType = InputStream;
+
+    fn class_init(class: &mut Self::Class) {
+        override_async::<Self>(class)
+    }
+}
+
+impl ObjectImpl for BoundedInputStream {}
+
+impl InputStreamImpl for BoundedInputStream {
+    fn read(
+        &self,
+        buffer: &mut [u8],
+        cancellable: Option<&Cancellable>,
+    ) -> Result<usize, glib::Error> {
+        let len = self.clamp(buffer.len())?;
+        let len = match self.base.lock().unwrap().as_ref() {
+            Some(base) => base.input_stream.0.read(&mut buffer[..len], cancellable)?,
+            None => return Ok(0),
+        };
+        self.count(len)
+    }
+
+    fn close(&self, cancellable: Option<&Cancellable>) -> Result<(), glib::Error> {
+        match self.base.lock().unwrap().take() {
+            Some(base) => match base.io_stream {
+                Some(io_stream) => io_stream.0.close(cancellable),
+                None => base.input_stream.0.close(cancellable),
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+impl ReadAsync for BoundedInputStream {
+    fn read_async(
+        &self,
+        len: usize,
+        priority: Priority,
+        cancellable: Option<&Cancellable>,
+        callback: impl FnOnce(Result<Bytes, glib::Error>) + 'static,
+    ) {
+        let len = match self.clamp(len) {
+            Ok(len) => len,
+            Err(e) => return callback(Err(e)),
+        };
+        let input_stream = match self.base.lock().unwrap().as_ref() {
+            Some(base) => base.input_stream.0.clone(),
+            None => return callback(Ok(Bytes::from_static(&[]))),
+        };
+        let this = self.obj().clone();
+        input_stream.read_bytes_async(len, priority, cancellable, move |result| {
+            callback(match result {
+                Ok(bytes) => this.imp().count(bytes.len()).map(|_| bytes),
+                Err(e) => Err(e),
+            })
+        })
+    }
+
+    fn close_async(
+        &self,
+        priority: Priority,
+        cancellable: Option<&Cancellable>,
+        callback: impl FnOnce(Result<(), glib::Error>) + 'static,
+    ) {
+        match self.base.lock().unwrap().take() {
+            Some(base) => match base.io_stream {
+                Some(io_stream) => io_stream.0.close_async(priority, cancellable, callback),
+                None => base
+                    .input_stream
+                    .0
+                    .close_async(priority, cancellable, callback),
+            },
+            None => callback(Ok(())),
+        }
+    }
+}
+
+// Tools
+
+fn limit_error(total: usize, limit: usize) -> glib::Error {
+    glib::Error::new(
+        super::Error::BytesTotal,
+        &format!("Bytes total limit reached: {total} / {limit}"),
+    )
+}
@@ -0,0 +1,68 @@
+pub mod sniff;
+pub use sniff::Sniff;
+
+/// Recommended bytes count to `sniff`
+pub const SNIFF_LEN: usize = 0x1000; // 4KB
+
+/// Guess content type for the first body bytes, using [content type](https://docs.gtk.org/gio/content-types.html) API
+/// * useful to detect the content, when the server returns generic (e.g. `application/octet-stream`) or wrong MIME
+/// * the `declared` MIME type parameters (e.g. `; charset=utf-8`) are ignored
+/// * the `declared` MIME type of `text/gemini` is never sniffed
+pub fn sniff(declared: &str, data: &[u8]) -> Sniff {
+    let declared = declared
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+    if declared == "text/gemini" || data.is_empty() {
+        return Sniff {
+            declared,
+            sniffed: None,
+            is_uncertain: false,
+        };
+    }
+    let (content_type, is_uncertain) = gio::content_type_guess(None::<&str>, data);
+    Sniff {
+        declared,
+        sniffed: gio::content_type_get_mime_type(&content_type)
+            .map(|mime| mime.to_string())
+            .filter(|mime| mime != "application/octet-stream"),
+        is_uncertain,
+    }
+}
+
+#[test]
+fn test() {
+    let s = sniff("text/gemini", b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR");
+    assert_eq!(s.sniffed, None);
+    assert_eq!(s.mime(), "text/gemini");
+
+    let s = sniff("application/octet-stream", b"");
+    assert_eq!(s.sniffed, None);
+    assert!(!s.is_mismatch());
+
+    let s = sniff("application/octet-stream", b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR");
+    assert_eq!(s.declared, "application/octet-stream");
+    assert!(s.is_generic());
+    assert_eq!(s.mime(), "image/png");
+    assert!(!s.is_mismatch()); // any type is `application/octet-stream`
+
+    let s = sniff("text/plain", b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR");
+    assert_eq!(s.mime(), "text/plain");
+    assert!(s.is_mismatch());
+
+    let s = sniff(
+        "Text/Gemini; charset=utf-8; lang=en",
+        b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR",
+    );
+    assert_eq!(s.declared, "text/gemini");
+    assert_eq!(s.sniffed, None);
+
+    let s = sniff(
+        "application/octet-stream; name=file",
+        b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR",
+    );
+    assert!(s.is_generic());
+    assert_eq!(s.mime(), "image/png");
+}
@@ -0,0 +1,38 @@
+/// Result of the `super::sniff` function
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sniff {
+    /// MIME type declared by the server, without parameters
+    pub declared: String,
+    /// MIME type guessed for the content bytes, if detected
+    pub sniffed: Option<String>,
+    /// Guess is not reliable (e.g. for the plain text)
+    pub is_uncertain: bool,
+}
+
+impl Sniff {
+    /// Check declared MIME type is generic (unknown binary data)
+    pub fn is_generic(&self) -> bool {
+        self.declared == "application/octet-stream"
+    }
+
+    /// Check sniffed MIME type does not match (or not subtype of) declared one
+    pub fn is_mismatch(&self) -> bool {
+        self.sniffed.as_ref().is_some_and(|sniffed| {
+            !gio::content_type_is_mime_type(sniffed, &self.declared)
+                && !gio::content_type_is_a(
+                    sniffed,
+                    &gio::content_type_from_mime_type(&self.declared)
+                        .map_or(self.declared.clone(), |c| c.to_string()),
+                )
+        })
+    }
+
+    /// Get recommended MIME type
+    /// * sniffed one for generic declaration, if certain; declared one by default
+    pub fn mime(&self) -> &str {
+        match self.sniffed {
+            Some(ref sniffed) if self.is_generic() && !self.is_uncertain => sniffed,
+            _ => &self.declared,
+        }
+    }
+}
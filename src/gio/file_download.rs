@@ -0,0 +1,274 @@
+pub mod error;
+pub use error::Error;
+
+use super::output_stream::{self, Size};
+use gio::{
+    Cancellable, File, FileCopyFlags, FileCreateFlags, FileOutputStream, IOStream, InputStream,
+    prelude::{FileExt, FileExtManual, IOStreamExt, OutputStreamExt},
+};
+use glib::{Bytes, Checksum, ChecksumType, Priority, object::IsA};
+use std::{cell::RefCell, rc::Rc};
+
+/// Asynchronously download all bytes from [IOStream](https://docs.gtk.org/gio/class.IOStream.html)
+/// to the [File](https://docs.gtk.org/gio/iface.File.html) destination
+/// * require `IOStream` reference to keep `Connection` active in async thread
+/// * see `from_input_stream_async` for details
+pub fn from_stream_async(
+    io_stream: impl IsA<IOStream>,
+    file: File,
+    checksum_type: ChecksumType,
+    cancellable: Cancellable,
+    priority: Priority,
+    size: Size,
+    (on_chunk, on_complete): (
+        impl Fn(Bytes, usize) + 'static, // on_chunk
+        impl FnOnce(Result<(File, String, usize), Error>) + 'static, // on_complete
+    ),
+) {
+    from_input_stream_async(
+        io_stream.input_stream(),
+        file,
+        checksum_type,
+        cancellable,
+        priority,
+        size,
+        (on_chunk, move |result| {
+            drop(io_stream); // keep `Connection` active until complete
+            on_complete(result)
+        }),
+    )
+}
+
+/// Asynchronously download all bytes from [InputStream](https://docs.gtk.org/gio/class.InputStream.html)
+/// to the [File](https://docs.gtk.org/gio/iface.File.html) destination
+///
+/// * the bytes are written to the temporary sibling file, that is renamed to the `file`
+///   on success only (replacing existing one), or deleted on failure or cancellation
+/// * calculate checksum of `checksum_type` on the fly
+/// * the temporary file is kept (closed) on `Error::Truncated`,
+///   so the caller may decide to keep or delete the received bytes
+///
+/// Return the `file`, hex string checksum and bytes total on success
+pub fn from_input_stream_async(
+    input_stream: impl IsA<InputStream>,
+    file: File,
+    checksum_type: ChecksumType,
+    cancellable: Cancellable,
+    priority: Priority,
+    size: Size,
+    (on_chunk, on_complete): (
+        impl Fn(Bytes, usize) + 'static, // on_chunk
+        impl FnOnce(Result<(File, String, usize), Error>) + 'static, // on_complete
+    ),
+) {
+    let checksum = match Checksum::new(checksum_type) {
+        Some(checksum) => Rc::new(RefCell::new(checksum)),
+        None => return on_complete(Err(Error::Checksum)),
+    };
+    let temporary = match temporary(&file) {
+        Some(temporary) => temporary,
+        None => return on_complete(Err(Error::Parent)),
+    };
+    temporary.clone().create_async(
+        FileCreateFlags::PRIVATE,
+        priority,
+        Some(&cancellable.clone()),
+        move |result| match result {
+            Ok(file_output_stream) => output_stream::from_input_stream_async(
+                input_stream,
+                file_output_stream.clone(),
+                cancellable.clone(),
+                priority,
+                size,
+                (
+                    {
+                        let checksum = checksum.clone();
+                        move |bytes: Bytes, total| {
+                            checksum.borrow_mut().update(&bytes);
+                            on_chunk(bytes, total)
+                        }
+                    },
+                    move |result| match result {
+                        Ok((_, total)) => file_output_stream.close_async(
+                            priority,
+                            Some(&cancellable.clone()),
+                            move |result| match result {
+                                Ok(()) => commit_async(
+                                    temporary.clone(),
+                                    &file.clone(),
+                                    priority,
+                                    &cancellable,
+                                    move |result| match result {
+                                        Ok(()) => {
+                                            on_complete(match checksum.borrow().clone().string() {
+                                                Some(checksum) => Ok((file, checksum, total)),
+                                                None => Err(Error::Checksum),
+                                            })
+                                        }
+                                        Err(e) => discard(
+                                            temporary,
+                                            None,
+                                            priority,
+                                            (Error::Move(e), on_complete),
+                                        ),
+                                    },
+                                ),
+                                Err(e) => discard(
+                                    temporary,
+                                    None,
+                                    priority,
+                                    (Error::Close(e), on_complete),
+                                ),
+                            },
+                        ),
+                        Err(output_stream::Error::Truncated(total)) => file_output_stream
+                            .close_async(priority, Cancellable::NONE, move |result| match result {
+                                Ok(()) => on_complete(Err(Error::Truncated(temporary, total))),
+                                Err(e) => discard(
+                                    temporary,
+                                    None,
+                                    priority,
+                                    (Error::Close(e), on_complete),
+                                ),
+                            }),
+                        Err(e) => discard(
+                            temporary,
+                            Some(file_output_stream),
+                            priority,
+                            (Error::OutputStream(e), on_complete),
+                        ),
+                    },
+                ),
+            ),
+            Err(e) => on_complete(Err(Error::Create(e))),
+        },
+    )
+}
+
+// Tools
+
+/// Build the temporary sibling `File` for given destination
+fn temporary(file: &File) -> Option<File> {
+    let parent = file.parent()?;
+    let name = file.basename()?;
+    Some(parent.child(format!(
+        ".{}.{:08x}.part",
+        name.to_string_lossy(),
+        glib::random_int()
+    )))
+}
+
+/// Asynchronously move the `temporary` file to the `file` destination, replace existing one
+/// * fallback to copy and delete, if GLib 2.72 `move_async` is not available (`v2_72` feature)
+/// * useful to keep the temporary file of `Error::Truncated`
+pub fn commit_async(
+    temporary: File,
+    file: &File,
+    priority: Priority,
+    cancellable: &Cancellable,
+    callback: impl FnOnce(Result<(), glib::Error>) + 'static,
+) {
+    #[cfg(feature = "v2_72")]
+    temporary.move_async(
+        file,
+        FileCopyFlags::OVERWRITE,
+        priority,
+        Some(cancellable),
+        None,
+        callback,
+    );
+    #[cfg(not(feature = "v2_72"))]
+    temporary.clone().copy_async(
+        file,
+        FileCopyFlags::OVERWRITE,
+        priority,
+        Some(cancellable),
+        None,
+        move |result| match result {
+            // ignore the delete result, as the destination is complete
+            Ok(()) => {
+                temporary.delete_async(priority, Cancellable::NONE, move |_| callback(Ok(())))
+            }
+            Err(e) => callback(Err(e)),
+        },
+    )
+}
+
+/// Close and delete the `temporary` file, then callback with `error`
+fn discard<T>(
+    temporary: File,
+    file_output_stream: Option<FileOutputStream>,
+    priority: Priority,
+    (error, on_complete): (Error, impl FnOnce(Result<T, Error>) + 'static),
+) {
+    match file_output_stream {
+        // ignore the close result, as the file is going to be deleted
+        Some(file_output_stream) => {
+            file_output_stream.close_async(priority, Cancellable::NONE, move |_| {
+                discard(temporary, None, priority, (error, on_complete))
+            })
+        }
+        None => temporary.delete_async(priority, Cancellable::NONE, move |_| {
+            on_complete(Err(error))
+        }),
+    }
+}
+
+#[test]
+fn test() {
+    use gio::MemoryInputStream;
+    use glib::MainContext;
+
+    fn t(source: &'static [u8], file: &File, size: Size) -> Result<(File, String, usize), Error> {
+        let result = Rc::new(RefCell::new(None));
+        from_input_stream_async(
+            MemoryInputStream::from_bytes(&Bytes::from_static(source)),
+            file.clone(),
+            ChecksumType::Sha256,
+            Cancellable::new(),
+            Priority::DEFAULT,
+            size,
+            (|_, _| {}, {
+                let result = result.clone();
+                move |r| {
+                    result.replace(Some(r));
+                }
+            }),
+        );
+        let c = MainContext::default();
+        while result.borrow().is_none() {
+            c.iteration(true);
+        }
+        result.take().unwrap()
+    }
+
+    let directory = glib::mkdtemp(glib::tmp_dir().join("ggemini-XXXXXX")).unwrap();
+    let file = File::for_path(directory.join("file"));
+
+    // success
+    let (f, checksum, total) = t(b"data", &file, Size::default()).unwrap();
+    assert_eq!(total, 4);
+    assert_eq!(
+        checksum,
+        glib::compute_checksum_for_data(ChecksumType::Sha256, b"data").unwrap()
+    );
+    assert_eq!(&*f.load_bytes(Cancellable::NONE).unwrap().0, b"data");
+
+    // failure keeps the previous file version
+    assert!(
+        t(
+            b"new data",
+            &file,
+            Size {
+                limit: Some(1),
+                ..Size::default()
+            }
+        )
+        .is_err()
+    );
+    assert_eq!(&*file.load_bytes(Cancellable::NONE).unwrap().0, b"data");
+
+    // no temporary files left
+    assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 1);
+    std::fs::remove_dir_all(directory).unwrap();
+}
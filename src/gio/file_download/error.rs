@@ -0,0 +1,45 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug)]
+pub enum Error {
+    Checksum,
+    Close(glib::Error),
+    Create(glib::Error),
+    Move(glib::Error),
+    OutputStream(crate::gio::output_stream::Error),
+    Parent,
+    /// Temporary file with bytes total written, when the server closed the connection
+    /// without TLS `close_notify` (possibly truncated)
+    Truncated(gio::File, usize),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Checksum => {
+                write!(f, "Checksum type not supported")
+            }
+            Self::Close(e) => {
+                write!(f, "Close error: {e}")
+            }
+            Self::Create(e) => {
+                write!(f, "Temporary file create error: {e}")
+            }
+            Self::Move(e) => {
+                write!(f, "Move error: {e}")
+            }
+            Self::OutputStream(e) => {
+                write!(f, "Output stream error: {e}")
+            }
+            Self::Parent => {
+                write!(f, "Parent directory required")
+            }
+            Self::Truncated(_, total) => {
+                write!(
+                    f,
+                    "Possibly truncated after {total} bytes: missing TLS close_notify"
+                )
+            }
+        }
+    }
+}
@@ -4,66 +4,38 @@ pub mod size;
 pub use error::Error;
 pub use size::Size;
 
-use gio::{
-    Cancellable, FileOutputStream, IOStream,
-    prelude::{IOStreamExt, InputStreamExt, OutputStreamExtManual},
-};
+use gio::{Cancellable, FileOutputStream, IOStream};
 use glib::{Bytes, Priority, object::IsA};
 
 /// Asynchronously move all bytes from [IOStream](https://docs.gtk.org/gio/class.IOStream.html)
 /// to [FileOutputStream](https://docs.gtk.org/gio/class.FileOutputStream.html)
 /// * require `IOStream` reference to keep `Connection` active in async thread
+/// * see `crate::gio::output_stream` for any other `OutputStream` type
 pub fn from_stream_async(
     io_stream: impl IsA<IOStream>,
     file_output_stream: FileOutputStream,
     cancellable: Cancellable,
     priority: Priority,
-    mut size: Size,
+    size: Size,
     (on_chunk, on_complete): (
         impl Fn(Bytes, usize) + 'static, // on_chunk
         impl FnOnce(Result<(FileOutputStream, usize), Error>) + 'static, // on_complete
     ),
 ) {
-    io_stream.input_stream().read_bytes_async(
-        size.chunk,
+    use crate::gio::output_stream::Error as E;
+    crate::gio::output_stream::from_stream_async(
+        io_stream,
+        file_output_stream,
+        cancellable,
         priority,
-        Some(&cancellable.clone()),
-        move |result| match result {
-            Ok(bytes) => {
-                size.total += bytes.len();
-                on_chunk(bytes.clone(), size.total);
-
-                if let Some(limit) = size.limit
-                    && size.total > limit
-                {
-                    return on_complete(Err(Error::BytesTotal(size.total, limit)));
-                }
-
-                if bytes.is_empty() {
-                    return on_complete(Ok((file_output_stream, size.total)));
-                }
-
-                // Make sure **all bytes** sent to the destination
-                // > A partial write is performed with the size of a message block, which is 16kB
-                // > https://docs.openssl.org/3.0/man3/SSL_write/#notes
-                file_output_stream.clone().write_all_async(
-                    bytes,
-                    priority,
-                    Some(&cancellable.clone()),
-                    move |result| match result {
-                        Ok(_) => from_stream_async(
-                            io_stream,
-                            file_output_stream,
-                            cancellable,
-                            priority,
-                            size,
-                            (on_chunk, on_complete),
-                        ),
-                        Err((b, e)) => on_complete(Err(Error::OutputStream(b, e))),
-                    },
-                )
-            }
-            Err(e) => on_complete(Err(Error::InputStream(e))),
-        },
+        size,
+        (on_chunk, move |result| {
+            on_complete(result.map_err(|e| match e {
+                E::BytesTotal(total, limit) => Error::BytesTotal(total, limit),
+                E::InputStream(_, e) => Error::InputStream(e),
+                E::OutputStream(_, b, e) => Error::OutputStream(b, e),
+                E::Truncated(total) => Error::Truncated(total),
+            }))
+        }),
     )
 }
@@ -5,6 +5,9 @@ pub enum Error {
     BytesTotal(usize, usize),
     InputStream(glib::Error),
     OutputStream(glib::Bytes, glib::Error),
+    /// Bytes total written, when the server closed the connection without TLS `close_notify`
+    /// (possibly truncated)
+    Truncated(usize),
 }
 
 impl Display for Error {
@@ -19,6 +22,12 @@ impl Display for Error {
             Self::OutputStream(_, e) => {
                 write!(f, "Output stream error: {e}")
             }
+            Self::Truncated(total) => {
+                write!(
+                    f,
+                    "Possibly truncated after {total} bytes: missing TLS close_notify"
+                )
+            }
         }
     }
 }
@@ -0,0 +1,44 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug)]
+pub enum Error {
+    Certificate,
+    Der(crate::gio::tls_certificate::der::Error),
+    Encrypted,
+    File(glib::Error),
+    Mismatch,
+    Password(glib::Error),
+    PrivateKey,
+    TlsCertificate(glib::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Certificate => {
+                write!(f, "Certificate not found")
+            }
+            Self::Der(e) => {
+                write!(f, "Malformed DER data: {e}")
+            }
+            Self::Encrypted => {
+                write!(f, "Private key is encrypted")
+            }
+            Self::File(e) => {
+                write!(f, "File error: {e}")
+            }
+            Self::Mismatch => {
+                write!(f, "Private key does not match the certificate")
+            }
+            Self::Password(e) => {
+                write!(f, "Wrong password: {e}")
+            }
+            Self::PrivateKey => {
+                write!(f, "Private key not found")
+            }
+            Self::TlsCertificate(e) => {
+                write!(f, "TLS certificate error: {e}")
+            }
+        }
+    }
+}
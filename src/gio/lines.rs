@@ -0,0 +1,172 @@
+pub mod error;
+pub mod line;
+pub mod size;
+
+pub use error::Error;
+pub use line::{Eol, Line};
+pub use size::Size;
+
+use gio::{
+    Cancellable, IOStream, TlsError,
+    prelude::{CancellableExt, IOStreamExt, InputStreamExt},
+};
+use glib::{Priority, object::IsA};
+
+/// Asynchronously read [IOStream](https://docs.gtk.org/gio/class.IOStream.html) line by line,
+/// callback every complete line on its arrival
+///
+/// **Useful for**
+/// * progressive render of `text/gemini` (or any other line-based) content,
+///   where the page could be appended to the view while loading
+/// * both `CRLF` and `LF` line endings, the last line without ending is returned on EOF
+///
+/// * require `IOStream` reference to keep `Connection` active in async thread
+pub fn from_stream_async(
+    io_stream: impl IsA<IOStream>,
+    priority: Priority,
+    cancellable: Cancellable,
+    size: Size,
+    (on_line, on_complete): (
+        impl Fn(Line, usize) + 'static,
+        impl FnOnce(Result<usize, Error>) + 'static,
+    ),
+) {
+    for_buffer_async(
+        Vec::with_capacity(size.chunk),
+        io_stream,
+        priority,
+        cancellable,
+        size,
+        (on_line, on_complete),
+    )
+}
+
+/// Continue `from_stream_async` with the incomplete line bytes collected by previous chunk
+fn for_buffer_async(
+    mut buffer: Vec<u8>,
+    io_stream: impl IsA<IOStream>,
+    priority: Priority,
+    cancellable: Cancellable,
+    mut size: Size,
+    (on_line, on_complete): (
+        impl Fn(Line, usize) + 'static,
+        impl FnOnce(Result<usize, Error>) + 'static,
+    ),
+) {
+    io_stream.input_stream().read_bytes_async(
+        size.chunk,
+        priority,
+        Some(&cancellable.clone()),
+        move |result| match result {
+            Ok(bytes) => {
+                let len = bytes.len(); // calculate once
+
+                // is end of stream
+                if len == 0 {
+                    if !buffer.is_empty() {
+                        on_line(Line::from_bytes(buffer), size.total);
+                    }
+                    return on_complete(Ok(size.total));
+                }
+
+                // prevent memory overflow
+                size.total += len;
+                if let Some(limit) = size.limit
+                    && size.total > limit
+                {
+                    return on_complete(Err(Error::BytesTotal(size.total, limit)));
+                }
+
+                // callback every complete line found
+                buffer.extend_from_slice(&bytes);
+                let mut start = 0;
+                while let Some(i) = buffer[start..].iter().position(|b| *b == b'\n') {
+                    if cancellable.is_cancelled() {
+                        return on_complete(Err(Error::Cancelled));
+                    }
+                    let end = start + i + 1;
+                    let line = Line::from_bytes(buffer[start..end].to_vec());
+                    if line.data.len() > size.line {
+                        return on_complete(Err(Error::LineLength(line.data.len(), size.line)));
+                    }
+                    on_line(line, size.total);
+                    start = end;
+                }
+                buffer.drain(..start);
+
+                // prevent memory overflow by the line without ending
+                if buffer.len() > size.line {
+                    return on_complete(Err(Error::LineLength(buffer.len(), size.line)));
+                }
+
+                // handle next chunk..
+                for_buffer_async(
+                    buffer,
+                    io_stream,
+                    priority,
+                    cancellable,
+                    size,
+                    (on_line, on_complete),
+                )
+            }
+            Err(e) if e.matches(TlsError::Eof) => {
+                on_complete(Err(Error::Truncated(size.total, buffer)))
+            }
+            Err(e) => on_complete(Err(Error::InputStream(e))),
+        },
+    )
+}
+
+#[test]
+fn test() {
+    use gio::{MemoryInputStream, MemoryOutputStream, SimpleIOStream};
+    use glib::{Bytes, MainContext};
+    use std::{cell::RefCell, rc::Rc};
+
+    let lines = Rc::new(RefCell::new(Vec::new()));
+    let result = Rc::new(RefCell::new(None));
+
+    from_stream_async(
+        SimpleIOStream::new(
+            &MemoryInputStream::from_bytes(&Bytes::from(b"# H\r\n\nlong line\nend")),
+            &MemoryOutputStream::new_resizable(),
+        ),
+        Priority::DEFAULT,
+        Cancellable::new(),
+        Size {
+            chunk: 3, // split lines between chunks
+            ..Size::default()
+        },
+        (
+            {
+                let lines = lines.clone();
+                move |line: Line, _| {
+                    lines
+                        .borrow_mut()
+                        .push((line.as_str().unwrap().to_string(), line.eol))
+                }
+            },
+            {
+                let result = result.clone();
+                move |r| {
+                    result.replace(Some(r));
+                }
+            },
+        ),
+    );
+
+    let c = MainContext::default();
+    while result.borrow().is_none() {
+        c.iteration(true);
+    }
+    assert!(result.take().unwrap().is_ok_and(|total| total == 19));
+    assert_eq!(
+        *lines.borrow(),
+        [
+            ("# H".to_string(), Eol::Crlf),
+            ("".to_string(), Eol::Lf),
+            ("long line".to_string(), Eol::Lf),
+            ("end".to_string(), Eol::None),
+        ]
+    );
+}
@@ -0,0 +1,37 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug)]
+pub enum Error {
+    BytesTotal(usize, usize),
+    Cancelled,
+    InputStream(glib::Error),
+    LineLength(usize, usize),
+    /// Bytes total with the last line not complete (if any),
+    /// when the server closed the connection without TLS `close_notify` (possibly truncated)
+    Truncated(usize, Vec<u8>),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::BytesTotal(total, limit) => {
+                write!(f, "Bytes total limit reached: {total} / {limit}")
+            }
+            Self::Cancelled => {
+                write!(f, "Operation cancelled")
+            }
+            Self::InputStream(e) => {
+                write!(f, "Input stream error: {e}")
+            }
+            Self::LineLength(length, limit) => {
+                write!(f, "Line length limit reached: {length} / {limit}")
+            }
+            Self::Truncated(total, _) => {
+                write!(
+                    f,
+                    "Possibly truncated after {total} bytes: missing TLS close_notify"
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+pub mod eol;
+pub use eol::Eol;
+
+use std::str::Utf8Error;
+
+/// Single line of the stream
+pub struct Line {
+    /// Line bytes without ending
+    pub data: Vec<u8>,
+    /// Original line ending
+    pub eol: Eol,
+}
+
+impl Line {
+    // Constructors
+
+    /// Create new `Self` from line bytes, including optional ending
+    pub fn from_bytes(mut data: Vec<u8>) -> Self {
+        let eol = if data.ends_with(b"\r\n") {
+            data.truncate(data.len() - 2);
+            Eol::Crlf
+        } else if data.ends_with(b"\n") {
+            data.truncate(data.len() - 1);
+            Eol::Lf
+        } else {
+            Eol::None
+        };
+        Self { data, eol }
+    }
+
+    // Getters
+
+    /// Get UTF-8 string of `Self` (without line ending)
+    pub fn as_str(&self) -> Result<&str, Utf8Error> {
+        std::str::from_utf8(&self.data)
+    }
+}
+
+#[test]
+fn test() {
+    fn t(source: &str, data: &str, eol: Eol) {
+        let l = Line::from_bytes(source.as_bytes().to_vec());
+        assert_eq!(l.as_str().unwrap(), data);
+        assert_eq!(l.eol, eol);
+    }
+    t("# Header\r\n", "# Header", Eol::Crlf);
+    t("# Header\n", "# Header", Eol::Lf);
+    t("# Header", "# Header", Eol::None);
+    t("\r\n", "", Eol::Crlf);
+    t("\n", "", Eol::Lf);
+    t("text\r", "text\r", Eol::None);
+}
@@ -0,0 +1,10 @@
+/// Line ending type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eol {
+    /// `\r\n`
+    Crlf,
+    /// `\n`
+    Lf,
+    /// Last line of the stream, without ending
+    None,
+}
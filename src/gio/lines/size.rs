@@ -0,0 +1,20 @@
+/// Mutable bytes count
+pub struct Size {
+    pub chunk: usize,
+    /// Max bytes for single line (without line ending)
+    pub line: usize,
+    /// `None` for unlimited
+    pub limit: Option<usize>,
+    pub total: usize,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Self {
+            chunk: 0x10000,       // 64KB
+            line: 0x10000,        // 64KB
+            limit: Some(0xfffff), // 1 MB
+            total: 0,
+        }
+    }
+}
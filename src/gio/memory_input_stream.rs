@@ -5,7 +5,7 @@ pub use error::Error;
 pub use size::Size;
 
 use gio::{
-    Cancellable, IOStream, MemoryInputStream,
+    Cancellable, IOStream, MemoryInputStream, TlsError,
     prelude::{IOStreamExt, InputStreamExt, MemoryInputStreamExt},
 };
 use glib::{Priority, object::IsA};
@@ -89,6 +89,9 @@ pub fn for_memory_input_stream_async(
                     (on_chunk, on_complete),
                 )
             }
+            Err(e) if e.matches(TlsError::Eof) => {
+                on_complete(Err(Error::Truncated(memory_input_stream, size.total)))
+            }
             Err(e) => on_complete(Err(Error::InputStream(memory_input_stream, e))),
         },
     )
@@ -4,6 +4,9 @@ use std::fmt::{Display, Formatter, Result};
 pub enum Error {
     BytesTotal(gio::MemoryInputStream, usize, usize),
     InputStream(gio::MemoryInputStream, glib::Error),
+    /// Server closed the connection without TLS `close_notify`,
+    /// the stream includes all bytes received (possibly truncated)
+    Truncated(gio::MemoryInputStream, usize),
 }
 
 impl Display for Error {
@@ -15,6 +18,12 @@ impl Display for Error {
             Self::InputStream(_, e) => {
                 write!(f, "Input stream error: {e}")
             }
+            Self::Truncated(_, total) => {
+                write!(
+                    f,
+                    "Possibly truncated after {total} bytes: missing TLS close_notify"
+                )
+            }
         }
     }
 }
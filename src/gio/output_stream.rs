@@ -0,0 +1,147 @@
+pub mod error;
+pub mod size;
+
+pub use error::Error;
+pub use size::Size;
+
+use gio::{
+    Cancellable, IOStream, InputStream, OutputStream, TlsError,
+    prelude::{IOStreamExt, InputStreamExt, OutputStreamExtManual},
+};
+use glib::{Bytes, Priority, object::IsA};
+
+/// Asynchronously move all bytes from [IOStream](https://docs.gtk.org/gio/class.IOStream.html)
+/// to any [OutputStream](https://docs.gtk.org/gio/class.OutputStream.html)
+/// (e.g. pipe, socket, converter or subprocess `stdin`)
+/// * require `IOStream` reference to keep `Connection` active in async thread
+/// * the `output_stream` is not closing on complete
+pub fn from_stream_async<O: IsA<OutputStream>>(
+    io_stream: impl IsA<IOStream>,
+    output_stream: O,
+    cancellable: Cancellable,
+    priority: Priority,
+    size: Size,
+    (on_chunk, on_complete): (
+        impl Fn(Bytes, usize) + 'static,                  // on_chunk
+        impl FnOnce(Result<(O, usize), Error>) + 'static, // on_complete
+    ),
+) {
+    from_input_stream_async(
+        io_stream.input_stream(),
+        output_stream,
+        cancellable,
+        priority,
+        size,
+        (on_chunk, move |result| {
+            drop(io_stream); // keep `Connection` active until complete
+            on_complete(result)
+        }),
+    )
+}
+
+/// Asynchronously move all bytes from any [InputStream](https://docs.gtk.org/gio/class.InputStream.html)
+/// to any [OutputStream](https://docs.gtk.org/gio/class.OutputStream.html)
+/// * the `output_stream` is not closing on complete
+pub fn from_input_stream_async<O: IsA<OutputStream>>(
+    input_stream: impl IsA<InputStream>,
+    output_stream: O,
+    cancellable: Cancellable,
+    priority: Priority,
+    mut size: Size,
+    (on_chunk, on_complete): (
+        impl Fn(Bytes, usize) + 'static,                  // on_chunk
+        impl FnOnce(Result<(O, usize), Error>) + 'static, // on_complete
+    ),
+) {
+    input_stream.clone().read_bytes_async(
+        size.chunk,
+        priority,
+        Some(&cancellable.clone()),
+        move |result| match result {
+            Ok(bytes) => {
+                size.total += bytes.len();
+                on_chunk(bytes.clone(), size.total);
+
+                if let Some(limit) = size.limit
+                    && size.total > limit
+                {
+                    return on_complete(Err(Error::BytesTotal(size.total, limit)));
+                }
+
+                if bytes.is_empty() {
+                    return on_complete(Ok((output_stream, size.total)));
+                }
+
+                // Make sure **all bytes** sent to the destination
+                // > A partial write is performed with the size of a message block, which is 16kB
+                // > https://docs.openssl.org/3.0/man3/SSL_write/#notes
+                output_stream.clone().write_all_async(
+                    bytes,
+                    priority,
+                    Some(&cancellable.clone()),
+                    move |result| match result {
+                        Ok(_) => from_input_stream_async(
+                            input_stream,
+                            output_stream,
+                            cancellable,
+                            priority,
+                            size,
+                            (on_chunk, on_complete),
+                        ),
+                        Err((b, e)) => {
+                            // the chunk was not written completely
+                            on_complete(Err(Error::OutputStream(size.total - b.len(), b, e)))
+                        }
+                    },
+                )
+            }
+            Err(e) if e.matches(TlsError::Eof) => on_complete(Err(Error::Truncated(size.total))),
+            Err(e) => on_complete(Err(Error::InputStream(size.total, e))),
+        },
+    )
+}
+
+#[test]
+fn test() {
+    use gio::{
+        MemoryInputStream, MemoryOutputStream,
+        prelude::{MemoryOutputStreamExt, OutputStreamExt},
+    };
+    use glib::MainContext;
+    use std::{cell::RefCell, rc::Rc};
+
+    fn t(limit: Option<usize>) -> Result<(MemoryOutputStream, usize), Error> {
+        let result = Rc::new(RefCell::new(None));
+        from_input_stream_async(
+            MemoryInputStream::from_bytes(&Bytes::from(b"abcdefg")),
+            MemoryOutputStream::new_resizable(),
+            Cancellable::new(),
+            Priority::DEFAULT,
+            Size {
+                chunk: 2,
+                limit,
+                ..Size::default()
+            },
+            (|_, _| {}, {
+                let result = result.clone();
+                move |r| {
+                    result.replace(Some(r));
+                }
+            }),
+        );
+        let c = MainContext::default();
+        while result.borrow().is_none() {
+            c.iteration(true);
+        }
+        result.take().unwrap()
+    }
+
+    let (m, total) = t(None).unwrap();
+    assert_eq!(total, 7);
+    m.close(gio::Cancellable::NONE).unwrap();
+    assert_eq!(&*m.steal_as_bytes(), b"abcdefg");
+
+    let e = t(Some(5)).unwrap_err();
+    assert!(matches!(e, Error::BytesTotal(6, 5)));
+    assert_eq!(e.total(), 6);
+}
@@ -0,0 +1,49 @@
+use std::fmt::{Display, Formatter, Result};
+
+/// Every member includes bytes total processed before failure
+#[derive(Debug)]
+pub enum Error {
+    /// Bytes total received, limit
+    BytesTotal(usize, usize),
+    /// Bytes total written
+    InputStream(usize, glib::Error),
+    /// Bytes total written (without the failed chunk)
+    OutputStream(usize, glib::Bytes, glib::Error),
+    /// Bytes total written, when the server closed the connection without TLS `close_notify`
+    /// (possibly truncated)
+    Truncated(usize),
+}
+
+impl Error {
+    /// Get bytes total processed before failure
+    pub fn total(&self) -> usize {
+        match self {
+            Self::BytesTotal(total, _) => *total,
+            Self::InputStream(total, _) => *total,
+            Self::OutputStream(total, ..) => *total,
+            Self::Truncated(total) => *total,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::BytesTotal(total, limit) => {
+                write!(f, "Bytes total limit reached: {total} / {limit}")
+            }
+            Self::InputStream(total, e) => {
+                write!(f, "Input stream error after {total} bytes: {e}")
+            }
+            Self::OutputStream(total, _, e) => {
+                write!(f, "Output stream error after {total} bytes: {e}")
+            }
+            Self::Truncated(total) => {
+                write!(
+                    f,
+                    "Possibly truncated after {total} bytes: missing TLS close_notify"
+                )
+            }
+        }
+    }
+}
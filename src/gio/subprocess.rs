@@ -0,0 +1,173 @@
+pub mod error;
+pub mod exit;
+
+pub use error::Error;
+pub use exit::Exit;
+
+use super::output_stream::{self, Size};
+use gio::{
+    Cancellable, IOErrorEnum, InputStream, Subprocess, SubprocessFlags,
+    prelude::{CancellableExt, OutputStreamExt},
+};
+use glib::{Bytes, Priority, object::IsA};
+use std::ffi::OsStr;
+
+/// Spawn new [Subprocess](https://docs.gtk.org/gio/class.Subprocess.html) for `argv`,
+/// then asynchronously move all bytes from [InputStream](https://docs.gtk.org/gio/class.InputStream.html)
+/// to its `stdin`, as they arrive
+///
+/// **Useful for**
+/// * streaming of audio or video content to the external player (e.g. `mpv -`)
+///
+/// * the `stdin` is closing on the end of stream, then wait for the subprocess exit
+/// * the subprocess exit before the end of stream (broken pipe) is not an error
+/// * the subprocess is forced to exit on cancel or any other failure
+///
+/// Return `Exit` status with bytes total written on success
+pub fn from_input_stream_async(
+    input_stream: impl IsA<InputStream>,
+    argv: &[&OsStr],
+    cancellable: Cancellable,
+    priority: Priority,
+    size: Size,
+    (on_chunk, on_complete): (
+        impl Fn(Bytes, usize) + 'static,                     // on_chunk
+        impl FnOnce(Result<(Exit, usize), Error>) + 'static, // on_complete
+    ),
+) {
+    let subprocess = match Subprocess::newv(argv, SubprocessFlags::STDIN_PIPE) {
+        Ok(subprocess) => subprocess,
+        Err(e) => return on_complete(Err(Error::Spawn(e))),
+    };
+    let stdin = match subprocess.stdin_pipe() {
+        Some(stdin) => stdin,
+        None => {
+            subprocess.force_exit();
+            return on_complete(Err(Error::Stdin));
+        }
+    };
+    output_stream::from_input_stream_async(
+        input_stream,
+        stdin,
+        cancellable.clone(),
+        priority,
+        size,
+        (on_chunk, move |result| match result {
+            Ok((stdin, total)) => {
+                stdin
+                    .clone()
+                    .close_async(priority, Some(&cancellable.clone()), move |_| {
+                        wait(subprocess, Ok(total), cancellable, on_complete)
+                    })
+            }
+            Err(output_stream::Error::OutputStream(total, _, e))
+                if e.matches(IOErrorEnum::BrokenPipe) =>
+            {
+                wait(subprocess, Ok(total), cancellable, on_complete)
+            }
+            Err(e) => {
+                subprocess.force_exit();
+                let result = Err(if cancellable.is_cancelled() {
+                    Error::Cancelled(e.total())
+                } else {
+                    Error::Copy(e)
+                });
+                // the subprocess is forced to exit, wait for it without cancellation
+                wait(subprocess, result, Cancellable::new(), on_complete)
+            }
+        }),
+    )
+}
+
+/// Wait for `subprocess` exit, then callback with `result` given
+/// * force the `subprocess` to exit on cancel
+fn wait(
+    subprocess: Subprocess,
+    result: Result<usize, Error>,
+    cancellable: Cancellable,
+    on_complete: impl FnOnce(Result<(Exit, usize), Error>) + 'static,
+) {
+    subprocess
+        .clone()
+        .wait_async(Some(&cancellable.clone()), move |wait| {
+            on_complete(match result {
+                Ok(total) => match wait {
+                    Ok(()) => Ok((Exit::from_subprocess(&subprocess), total)),
+                    Err(_) if cancellable.is_cancelled() => {
+                        subprocess.force_exit();
+                        Err(Error::Cancelled(total))
+                    }
+                    Err(e) => Err(Error::Wait(e)),
+                },
+                Err(e) => Err(e),
+            })
+        })
+}
+
+#[test]
+fn test() {
+    use gio::MemoryInputStream;
+    use glib::MainContext;
+    use std::{cell::RefCell, rc::Rc};
+
+    fn t(argv: &[&OsStr], data: &'static [u8]) -> Result<(Exit, usize), Error> {
+        t_cancellable(argv, data, Cancellable::new())
+    }
+
+    fn t_cancellable(
+        argv: &[&OsStr],
+        data: &'static [u8],
+        cancellable: Cancellable,
+    ) -> Result<(Exit, usize), Error> {
+        let result = Rc::new(RefCell::new(None));
+        from_input_stream_async(
+            MemoryInputStream::from_bytes(&Bytes::from_static(data)),
+            argv,
+            cancellable,
+            Priority::DEFAULT,
+            Size::default(),
+            (|_, _| {}, {
+                let result = result.clone();
+                move |r| {
+                    result.replace(Some(r));
+                }
+            }),
+        );
+        let c = MainContext::default();
+        while result.borrow().is_none() {
+            c.iteration(true);
+        }
+        result.take().unwrap()
+    }
+
+    let (exit, total) = t(&[OsStr::new("cat")], b"data").unwrap();
+    assert_eq!(exit, Exit::Code(0));
+    assert_eq!(total, 4);
+
+    let (exit, _) = t(
+        &[OsStr::new("sh"), OsStr::new("-c"), OsStr::new("exit 3")],
+        b"",
+    )
+    .unwrap();
+    assert_eq!(exit, Exit::Code(3));
+
+    assert!(matches!(
+        t(&[OsStr::new("ggemini-not-found")], b""),
+        Err(Error::Spawn(_))
+    ));
+
+    // cancel while waiting for exit
+    let cancellable = Cancellable::new();
+    glib::timeout_add_local_once(std::time::Duration::from_millis(100), {
+        let cancellable = cancellable.clone();
+        move || cancellable.cancel()
+    });
+    assert!(matches!(
+        t_cancellable(
+            &[OsStr::new("sh"), OsStr::new("-c"), OsStr::new("sleep 10")],
+            b"data",
+            cancellable
+        ),
+        Err(Error::Cancelled(4))
+    ));
+}
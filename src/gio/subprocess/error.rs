@@ -0,0 +1,33 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug)]
+pub enum Error {
+    /// Bytes total written before cancel
+    Cancelled(usize),
+    Copy(crate::gio::output_stream::Error),
+    Spawn(glib::Error),
+    Stdin,
+    Wait(glib::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Cancelled(total) => {
+                write!(f, "Operation cancelled after {total} bytes")
+            }
+            Self::Copy(e) => {
+                write!(f, "Copy error: {e}")
+            }
+            Self::Spawn(e) => {
+                write!(f, "Spawn error: {e}")
+            }
+            Self::Stdin => {
+                write!(f, "Subprocess stdin pipe unavailable")
+            }
+            Self::Wait(e) => {
+                write!(f, "Wait error: {e}")
+            }
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use gio::Subprocess;
+
+/// Exit status of the [Subprocess](https://docs.gtk.org/gio/class.Subprocess.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    /// Normal exit with status code
+    Code(i32),
+    /// Terminated by signal number
+    Signal(i32),
+}
+
+impl Exit {
+    /// Create new `Self` for the `subprocess` exited
+    pub fn from_subprocess(subprocess: &Subprocess) -> Self {
+        if subprocess.has_signaled() {
+            Self::Signal(subprocess.term_sig())
+        } else {
+            Self::Code(subprocess.exit_status())
+        }
+    }
+
+    /// Check `Self` is successful exit (code `0`)
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Code(0))
+    }
+}
@@ -0,0 +1,113 @@
+pub mod der;
+pub mod error;
+pub mod info;
+
+pub use error::Error;
+pub use info::Info;
+
+use gio::{SocketConnectable, TlsCertificate, TlsCertificateFlags, prelude::TlsCertificateExt};
+use glib::ChecksumType;
+
+/// Get DER data of given [TlsCertificate](https://docs.gtk.org/gio/class.TlsCertificate.html)
+pub fn der(certificate: &TlsCertificate) -> Result<Vec<u8>, Error> {
+    match certificate.certificate() {
+        Some(bytes) => Ok(bytes.to_vec()),
+        None => Err(Error::Der),
+    }
+}
+
+/// Create new [TlsCertificate](https://docs.gtk.org/gio/class.TlsCertificate.html)
+/// from the certificate DER data (e.g. returned by `der`)
+pub fn from_der(der: &[u8]) -> Result<TlsCertificate, glib::Error> {
+    TlsCertificate::from_pem(&pem(der))
+}
+
+/// Get fingerprint (lowercase hex) of the certificate DER data, e.g. `ChecksumType::Sha256`
+pub fn fingerprint(certificate: &TlsCertificate, checksum: ChecksumType) -> Result<String, Error> {
+    checksum_for(&der(certificate)?, checksum)
+}
+
+/// Get [SubjectPublicKeyInfo](https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.7)
+/// DER data of given certificate
+/// * unlike the certificate fingerprint, it persists on the certificate renewal with the same key
+pub fn spki(certificate: &TlsCertificate) -> Result<Vec<u8>, Error> {
+    match der::spki(&der(certificate)?) {
+        Ok(spki) => Ok(spki.to_vec()),
+        Err(e) => Err(Error::Spki(e)),
+    }
+}
+
+/// Get fingerprint (lowercase hex) of the certificate SubjectPublicKeyInfo DER data
+pub fn spki_fingerprint(
+    certificate: &TlsCertificate,
+    checksum: ChecksumType,
+) -> Result<String, Error> {
+    checksum_for(&spki(certificate)?, checksum)
+}
+
+/// Get subjectAltName DNS entries of given certificate
+pub fn dns_names(certificate: &TlsCertificate) -> Vec<String> {
+    certificate
+        .dns_names()
+        .iter()
+        .map(|name| String::from_utf8_lossy(name).into_owned())
+        .collect()
+}
+
+/// Check given certificate is self-signed
+/// * the subject matches the issuer, and the certificate verifies itself as the CA
+pub fn is_self_signed(certificate: &TlsCertificate) -> bool {
+    certificate.subject_name() == certificate.issuer_name()
+        && !certificate
+            .verify(None::<&SocketConnectable>, Some(certificate))
+            .contains(TlsCertificateFlags::UNKNOWN_CA)
+}
+
+// Tools
+
+/// Encode certificate `der` data as PEM
+fn pem(der: &[u8]) -> String {
+    let base64 = glib::base64_encode(der);
+    let mut pem = String::from("-----BEGIN CERTIFICATE-----\n");
+    for line in base64.as_bytes().chunks(64) {
+        pem.push_str(&String::from_utf8_lossy(line));
+        pem.push('\n');
+    }
+    pem.push_str("-----END CERTIFICATE-----\n");
+    pem
+}
+
+/// Get lowercase hex checksum for `data`
+fn checksum_for(data: &[u8], checksum: ChecksumType) -> Result<String, Error> {
+    match glib::compute_checksum_for_data(checksum, data) {
+        Some(hex) => Ok(hex.into()),
+        None => Err(Error::Checksum),
+    }
+}
+
+#[test]
+fn test() {
+    // self-signed `CN=example.org` (EC P-256) with `example.org`, `*.example.org` DNS names
+    const DER: &str = "MIIBqDCCAU6gAwIBAgIUQtxwQ0HgTzmcRhc6m4zKppBzaNMwCgYIKoZIzj0EAwIwFjEUMBIGA1UEAwwLZXhhbXBsZS5vcmcwHhcNMjYxMDE4MTk1NDU3WhcNMzYxMDE1MTk1NDU3WjAWMRQwEgYDVQQDDAtleGFtcGxlLm9yZzBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABAIC6ZsYsXUkzibAp02hGef/rHiOuVt3PDa61yIbGZDE5xy5SqPG0uM+EuiNwNkstSXUsvCD5Rdp4bpLLc8Lb7GjejB4MB0GA1UdDgQWBBRbcaBkeQ9RxESAyMdXUdfM3ykyfjAfBgNVHSMEGDAWgBRbcaBkeQ9RxESAyMdXUdfM3ykyfjAPBgNVHRMBAf8EBTADAQH/MCUGA1UdEQQeMByCC2V4YW1wbGUub3Jngg0qLmV4YW1wbGUub3JnMAoGCCqGSM49BAMCA0gAMEUCIQCTSNEKR3LVK/6XN2IlRIAm7Zb/dan2c2YNMqkBUwUdDAIgXyiKCjTcWWty3nO3RQmG6fbitJAVFr+RH19w4Z8lauc=";
+
+    let data = glib::base64_decode(DER);
+    assert_eq!(
+        checksum_for(&data, ChecksumType::Sha256).unwrap(),
+        "971944146bc14b2a43d85ddfefe8315ccf7d082f944af17e5e692e42a9b1e269"
+    );
+    assert_eq!(
+        checksum_for(&data, ChecksumType::Sha1).unwrap(),
+        "44f7fb479ce9a84c3d14d2911f0ff1a4bd31dadd"
+    );
+    assert_eq!(
+        checksum_for(der::spki(&data).unwrap(), ChecksumType::Sha256).unwrap(),
+        "db32000be13a4aa9d1ac53ee8e0e94be0494b2623321f2ed58dc3a74ee3b0ef4"
+    );
+    let pem = pem(&data);
+    assert!(pem.starts_with("-----BEGIN CERTIFICATE-----\nMIIBqDCCAU6gAwIBAgIU"));
+    assert!(pem.lines().all(|line| line.len() <= 64));
+    let blocks = crate::gio::identity::pem::blocks(&pem);
+    assert_eq!(blocks.len(), 1);
+    assert!(blocks[0].is_certificate());
+    assert_eq!(blocks[0].data, data);
+}
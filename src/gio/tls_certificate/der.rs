@@ -0,0 +1,176 @@
+//! Minimal [DER](https://www.itu.int/rec/T-REC-X.690) reader,
+//! for the certificate fields not exposed by GIO
+
+pub mod error;
+pub use error::Error;
+
+const SEQUENCE: u8 = 0x30;
+const INTEGER: u8 = 0x02;
+const BIT_STRING: u8 = 0x03;
+const OCTET_STRING: u8 = 0x04;
+const OID: u8 = 0x06;
+const VERSION: u8 = 0xa0; // [0] EXPLICIT
+const PUBLIC_KEY: u8 = 0xa1; // [1] EXPLICIT, SEC1
+
+/// rsaEncryption (1.2.840.113549.1.1.1)
+const OID_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+/// id-ecPublicKey (1.2.840.10045.2.1)
+const OID_EC: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+
+/// Get [SubjectPublicKeyInfo](https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.7)
+/// element (tag, length and value) from the certificate DER data
+pub fn spki(certificate: &[u8]) -> Result<&[u8], Error> {
+    let (certificate, _) = value(certificate, SEQUENCE)?;
+    let (mut tbs, _) = value(certificate, SEQUENCE)?;
+    if tbs.first() == Some(&VERSION) {
+        tbs = skip(tbs)?;
+    }
+    tbs = skip_tag(tbs, INTEGER)?; // serialNumber
+    tbs = skip_tag(tbs, SEQUENCE)?; // signature
+    tbs = skip_tag(tbs, SEQUENCE)?; // issuer
+    tbs = skip_tag(tbs, SEQUENCE)?; // validity
+    tbs = skip_tag(tbs, SEQUENCE)?; // subject
+    let (header, len) = header(tbs)?;
+    if tbs[0] != SEQUENCE {
+        return Err(Error::Tag(SEQUENCE, tbs[0]));
+    }
+    Ok(&tbs[..header + len])
+}
+
+/// Get the public key material from the SubjectPublicKeyInfo element (see `spki`)
+/// * the RSA modulus, or the EC point
+/// * `None` for other key types
+pub fn public_key(spki: &[u8]) -> Result<Option<&[u8]>, Error> {
+    let (spki, _) = value(spki, SEQUENCE)?;
+    let (algorithm, next) = value(spki, SEQUENCE)?;
+    let (oid, _) = value(algorithm, OID)?;
+    let (bits, _) = value(next, BIT_STRING)?;
+    let key = bits.get(1..).ok_or(Error::End)?; // skip unused bits byte
+    Ok(match oid {
+        OID_RSA => Some(value(value(key, SEQUENCE)?.0, INTEGER)?.0),
+        OID_EC => Some(key),
+        _ => None,
+    })
+}
+
+/// Get the public key material (see `public_key`) from the private key DER data:
+/// PKCS#8, PKCS#1 (RSA) or SEC1 (EC)
+/// * `None` for other key types, or the EC key without the public key included
+pub fn private_key(key: &[u8]) -> Result<Option<&[u8]>, Error> {
+    let (sequence, _) = value(key, SEQUENCE)?;
+    let next = skip_tag(sequence, INTEGER)?; // version
+    match next.first() {
+        // PKCS#8
+        Some(&SEQUENCE) => {
+            let (algorithm, next) = value(next, SEQUENCE)?;
+            let (oid, _) = value(algorithm, OID)?;
+            let (key, _) = value(next, OCTET_STRING)?;
+            match oid {
+                OID_RSA => rsa_private_key(key),
+                OID_EC => ec_private_key(key),
+                _ => Ok(None),
+            }
+        }
+        Some(&INTEGER) => rsa_private_key(key),
+        Some(&OCTET_STRING) => ec_private_key(key),
+        Some(tag) => Err(Error::Tag(SEQUENCE, *tag)),
+        None => Err(Error::End),
+    }
+}
+
+// Tools
+
+/// Get the modulus of PKCS#1 RSA private key
+fn rsa_private_key(key: &[u8]) -> Result<Option<&[u8]>, Error> {
+    let (sequence, _) = value(key, SEQUENCE)?;
+    let next = skip_tag(sequence, INTEGER)?; // version
+    Ok(Some(value(next, INTEGER)?.0))
+}
+
+/// Get the public key of SEC1 EC private key, if included
+fn ec_private_key(key: &[u8]) -> Result<Option<&[u8]>, Error> {
+    let (sequence, _) = value(key, SEQUENCE)?;
+    let mut next = skip_tag(sequence, INTEGER)?; // version
+    next = skip_tag(next, OCTET_STRING)?; // privateKey
+    while let Some(tag) = next.first() {
+        if *tag == PUBLIC_KEY {
+            let (bits, _) = value(value(next, PUBLIC_KEY)?.0, BIT_STRING)?;
+            return Ok(Some(bits.get(1..).ok_or(Error::End)?));
+        }
+        next = skip(next)?;
+    }
+    Ok(None)
+}
+
+/// Get element value for expected `tag` with the data left after
+fn value(data: &[u8], tag: u8) -> Result<(&[u8], &[u8]), Error> {
+    let (header, len) = header(data)?;
+    if data[0] != tag {
+        return Err(Error::Tag(tag, data[0]));
+    }
+    Ok((&data[header..header + len], &data[header + len..]))
+}
+
+/// Skip element for expected `tag`, get the data left after
+fn skip_tag(data: &[u8], tag: u8) -> Result<&[u8], Error> {
+    value(data, tag).map(|(_, next)| next)
+}
+
+/// Skip element of any tag, get the data left after
+fn skip(data: &[u8]) -> Result<&[u8], Error> {
+    let (header, len) = header(data)?;
+    Ok(&data[header + len..])
+}
+
+/// Get element header length and value length
+/// * the value is guaranteed to fit `data`
+fn header(data: &[u8]) -> Result<(usize, usize), Error> {
+    let first = *data.get(1).ok_or(Error::End)?;
+    let (header, len) = if first < 0x80 {
+        (2, first as usize)
+    } else {
+        let n = (first & 0x7f) as usize;
+        if n == 0 || n > 4 {
+            return Err(Error::Length);
+        }
+        let bytes = data.get(2..2 + n).ok_or(Error::End)?;
+        let mut len = 0usize;
+        for b in bytes {
+            len = len
+                .checked_mul(0x100)
+                .and_then(|len| len.checked_add(*b as usize))
+                .ok_or(Error::Length)?;
+        }
+        (2 + n, len)
+    };
+    match header.checked_add(len) {
+        Some(total) if total <= data.len() => Ok((header, len)),
+        Some(_) => Err(Error::End),
+        None => Err(Error::Length),
+    }
+}
+
+#[test]
+fn test() {
+    // SEQUENCE { SEQUENCE { [0] { INTEGER 2 }, INTEGER 1, SEQUENCE x4, SEQUENCE { NULL } } }
+    let data = [
+        0x30, 0x16, 0x30, 0x14, 0xa0, 0x03, 0x02, 0x01, 0x02, 0x02, 0x01, 0x01, 0x30, 0x00, 0x30,
+        0x00, 0x30, 0x00, 0x30, 0x00, 0x30, 0x02, 0x05, 0x00,
+    ];
+    assert_eq!(spki(&data), Ok(&[0x30, 0x02, 0x05, 0x00][..]));
+
+    // truncated
+    assert_eq!(spki(&data[..10]), Err(Error::End));
+
+    // long form length
+    assert_eq!(header(&[0x30, 0x81, 0x01, 0x00]), Ok((3, 1)));
+    assert_eq!(header(&[0x30, 0x80]), Err(Error::Length));
+    assert_eq!(
+        header(&[0x30, 0x84, 0xff, 0xff, 0xff, 0xff]),
+        Err(if usize::BITS > 32 {
+            Error::End
+        } else {
+            Error::Length
+        })
+    );
+}
@@ -0,0 +1,24 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    End,
+    Length,
+    Tag(u8, u8),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::End => {
+                write!(f, "Unexpected end of data")
+            }
+            Self::Length => {
+                write!(f, "Unsupported length encoding")
+            }
+            Self::Tag(expected, found) => {
+                write!(f, "Unexpected tag: {found:#04x}, expected: {expected:#04x}")
+            }
+        }
+    }
+}
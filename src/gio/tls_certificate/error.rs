@@ -0,0 +1,24 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug)]
+pub enum Error {
+    Checksum,
+    Der,
+    Spki(super::der::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Checksum => {
+                write!(f, "Unsupported checksum type")
+            }
+            Self::Der => {
+                write!(f, "Certificate DER data not found")
+            }
+            Self::Spki(e) => {
+                write!(f, "SubjectPublicKeyInfo error: {e}")
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use super::Error;
+use gio::{TlsCertificate, prelude::TlsCertificateExt};
+use glib::{ChecksumType, DateTime, GString};
+
+/// Server certificate details, e.g. for the certificate dialog or TOFU prompt
+#[derive(Debug, Clone)]
+pub struct Info {
+    /// SHA-256 fingerprint (lowercase hex) of the DER data
+    pub sha256: String,
+    /// SHA-1 fingerprint (lowercase hex) of the DER data
+    pub sha1: String,
+    /// SHA-256 fingerprint (lowercase hex) of the SubjectPublicKeyInfo
+    pub spki_sha256: String,
+    /// Subject DN
+    pub subject_name: Option<GString>,
+    /// Issuer DN
+    pub issuer_name: Option<GString>,
+    pub not_valid_before: Option<DateTime>,
+    pub not_valid_after: Option<DateTime>,
+    /// subjectAltName DNS entries
+    pub dns_names: Vec<String>,
+    pub is_self_signed: bool,
+}
+
+impl Info {
+    // Constructors
+
+    /// Create new `Self` for given [TlsCertificate](https://docs.gtk.org/gio/class.TlsCertificate.html)
+    pub fn from_certificate(certificate: &TlsCertificate) -> Result<Self, Error> {
+        Ok(Self {
+            sha256: super::fingerprint(certificate, ChecksumType::Sha256)?,
+            sha1: super::fingerprint(certificate, ChecksumType::Sha1)?,
+            spki_sha256: super::spki_fingerprint(certificate, ChecksumType::Sha256)?,
+            subject_name: certificate.subject_name(),
+            issuer_name: certificate.issuer_name(),
+            not_valid_before: certificate.not_valid_before(),
+            not_valid_after: certificate.not_valid_after(),
+            dns_names: super::dns_names(certificate),
+            is_self_signed: super::is_self_signed(certificate),
+        })
+    }
+}
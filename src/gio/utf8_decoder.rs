@@ -0,0 +1,126 @@
+pub mod error;
+pub mod mode;
+
+pub use error::Error;
+pub use mode::Mode;
+
+/// Decode UTF-8 text from chunks, as they arrive
+/// * every multibyte character split between chunks is kept in buffer,
+///   until the next chunk complete it
+/// * useful for progressive render of the content received by
+///   `file_output_stream` / `memory_input_stream` chunk callbacks
+pub struct Utf8Decoder {
+    /// Incomplete trailing sequence of the previous chunk
+    buffer: Vec<u8>,
+    mode: Mode,
+}
+
+impl Default for Utf8Decoder {
+    fn default() -> Self {
+        Self::new(Mode::Replace)
+    }
+}
+
+impl Utf8Decoder {
+    // Constructors
+
+    /// Create new `Self` with given invalid sequences handle `Mode`
+    pub fn new(mode: Mode) -> Self {
+        Self {
+            buffer: Vec::with_capacity(4),
+            mode,
+        }
+    }
+
+    // Actions
+
+    /// Decode next `chunk`, return complete UTF-8 text only
+    /// * incomplete trailing sequence would be returned with the next chunk
+    pub fn decode(&mut self, chunk: &[u8]) -> Result<String, Error> {
+        let mut bytes = std::mem::take(&mut self.buffer);
+        bytes.extend_from_slice(chunk);
+
+        let mut text = String::with_capacity(bytes.len());
+        let mut start = 0;
+        while start < bytes.len() {
+            match std::str::from_utf8(&bytes[start..]) {
+                Ok(s) => {
+                    text.push_str(s);
+                    break;
+                }
+                Err(e) => {
+                    let valid = start + e.valid_up_to();
+                    // bytes until `valid_up_to` are always valid UTF-8
+                    if let Ok(s) = std::str::from_utf8(&bytes[start..valid]) {
+                        text.push_str(s)
+                    }
+                    match e.error_len() {
+                        // incomplete sequence at the end of chunk
+                        None => {
+                            self.buffer.extend_from_slice(&bytes[valid..]);
+                            break;
+                        }
+                        Some(len) => match self.mode {
+                            Mode::Replace => {
+                                text.push(char::REPLACEMENT_CHARACTER);
+                                start = valid + len;
+                            }
+                            Mode::Strict => return Err(Error::Invalid(text, e)),
+                        },
+                    }
+                }
+            }
+        }
+        Ok(text)
+    }
+
+    /// Complete decode (e.g. on the end of stream)
+    /// * return `Ok` with text for the incomplete trailing sequence left, if any
+    pub fn finish(self) -> Result<String, Error> {
+        if self.buffer.is_empty() {
+            return Ok(String::new());
+        }
+        match self.mode {
+            Mode::Replace => Ok(char::REPLACEMENT_CHARACTER.to_string()),
+            Mode::Strict => Err(Error::Incomplete(self.buffer)),
+        }
+    }
+
+    // Getters
+
+    /// Get bytes of incomplete sequence, waiting for the next chunk
+    pub fn pending(&self) -> &[u8] {
+        &self.buffer
+    }
+}
+
+#[test]
+fn test() {
+    const TEXT: &str = "ab ё 中 🦀!";
+
+    // split source at every position
+    let b = TEXT.as_bytes();
+    for i in 0..b.len() {
+        for j in i..b.len() {
+            let mut d = Utf8Decoder::new(Mode::Strict);
+            let mut s = String::new();
+            s.push_str(&d.decode(&b[..i]).unwrap());
+            s.push_str(&d.decode(&b[i..j]).unwrap());
+            s.push_str(&d.decode(&b[j..]).unwrap());
+            assert!(d.pending().is_empty());
+            s.push_str(&d.finish().unwrap());
+            assert_eq!(s, TEXT);
+        }
+    }
+
+    // invalid sequences
+    let mut d = Utf8Decoder::new(Mode::Replace);
+    assert_eq!(d.decode(b"a\xffb\xf0\x9f").unwrap(), "a\u{fffd}b");
+    assert_eq!(d.pending(), b"\xf0\x9f");
+    assert_eq!(d.finish().unwrap(), "\u{fffd}");
+
+    let mut d = Utf8Decoder::new(Mode::Strict);
+    assert!(matches!(d.decode(b"a\xffb"), Err(Error::Invalid(s, _)) if s == "a"));
+    assert!(d.decode(b"\xf0\x9f").unwrap().is_empty());
+    assert!(matches!(d.finish(), Err(Error::Incomplete(b)) if b == b"\xf0\x9f"));
+}
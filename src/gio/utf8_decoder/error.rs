@@ -0,0 +1,25 @@
+use std::{
+    fmt::{Display, Formatter, Result},
+    str::Utf8Error,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    /// Incomplete sequence left on finish
+    Incomplete(Vec<u8>),
+    /// Invalid sequence, with the valid text decoded before
+    Invalid(String, Utf8Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Incomplete(bytes) => {
+                write!(f, "Incomplete UTF-8 sequence: {bytes:?}")
+            }
+            Self::Invalid(_, e) => {
+                write!(f, "Invalid UTF-8 sequence: {e}")
+            }
+        }
+    }
+}
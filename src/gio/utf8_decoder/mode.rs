@@ -0,0 +1,8 @@
+/// Invalid UTF-8 sequences handle mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Replace invalid sequence with `U+FFFD` character
+    Replace,
+    /// Return `Err` on invalid sequence
+    Strict,
+}